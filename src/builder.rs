@@ -0,0 +1,199 @@
+use crate::Iter;
+
+/// A ring buffer whose capacity is chosen at runtime, mirroring the core `RingBuffer` API.
+///
+/// Unlike `RingBuffer`, the one free slot is allocated on top of `capacity`,
+/// so `RingBufferBuilder::new(n)` holds exactly `n` elements.
+pub struct RingBufferBuilder<T> {
+    data: Box<[Option<T>]>,
+    front: usize,
+    back: usize,
+}
+
+impl<T> RingBufferBuilder<T> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            data: (0..=capacity).map(|_| None).collect(),
+            front: 0,
+            back: 0,
+        }
+    }
+
+    pub fn push(&mut self, item: T) -> bool {
+        if self.is_full() {
+            return false;
+        }
+        self.back = (self.back + 1) % self.data.len();
+        self.data[self.back] = Some(item);
+        true
+    }
+
+    pub fn push_front(&mut self, item: T) -> bool {
+        if self.is_full() {
+            return false;
+        }
+        self.data[self.front] = Some(item);
+        self.front = if self.front == 0 {
+            self.data.len() - 1
+        } else {
+            self.front - 1
+        };
+        true
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        let item = self.data[self.back].take();
+        self.back = if self.back == 0 {
+            self.data.len() - 1
+        } else {
+            self.back - 1
+        };
+        item
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        self.front = (self.front + 1) % self.data.len();
+        self.data[self.front].take()
+    }
+
+    /// Returns the element at the logical `index`, where 0 is the front.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.len() {
+            return None;
+        }
+        self.data[(self.front + 1 + index) % self.data.len()].as_ref()
+    }
+
+    /// Iterates from front to back, so the n-th item yielded is the one `get(n)` returns.
+    pub fn iter(&self) -> Iter<'_, T> {
+        let (head, tail) = self.as_slices();
+        Iter {
+            head: head.iter(),
+            tail: tail.iter(),
+        }
+    }
+
+    pub fn get_front_ref(&self) -> &Option<T> {
+        if self.is_empty() {
+            return &None;
+        }
+        &self.data[(self.front + 1) % self.data.len()]
+    }
+
+    pub fn get_back_ref(&self) -> &Option<T> {
+        if self.is_empty() {
+            return &None;
+        }
+        &self.data[self.back]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.front == self.back
+    }
+
+    pub fn is_full(&self) -> bool {
+        (self.back + 1) % self.data.len() == self.front
+    }
+
+    pub fn len(&self) -> usize {
+        (self.back + self.data.len() - self.front) % self.data.len()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.data.len() - 1
+    }
+
+    fn as_slices(&self) -> (&[Option<T>], &[Option<T>]) {
+        if self.is_empty() {
+            return (&[], &[]);
+        }
+        let first = (self.front + 1) % self.data.len();
+        if first <= self.back {
+            (&self.data[first..=self.back], &[])
+        } else {
+            (&self.data[first..], &self.data[..=self.back])
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_runtime_capacity() {
+        for capacity in [1, 3, 100] {
+            let mut buf = RingBufferBuilder::new(capacity);
+
+            assert_eq!(
+                buf.capacity(),
+                capacity,
+                "The RingBufferBuilder should hold exactly the requested capacity!"
+            );
+            for i in 0..capacity {
+                assert!(buf.push(i), "Expected to be able to push {capacity} items!");
+            }
+            assert!(
+                !buf.push(capacity),
+                "Expected the RingBufferBuilder to be full after {capacity} items!"
+            );
+            assert_eq!(buf.len(), capacity, "Expected len() to match the pushes!");
+        }
+    }
+
+    #[test]
+    fn test_push_pop_iter() {
+        let mut buf = RingBufferBuilder::new(5);
+
+        assert!(
+            buf.is_empty(),
+            "RingBufferBuilder should be empty directly after init!"
+        );
+        assert_eq!(buf.pop(), None, "Expected nothing to pop after init!");
+
+        buf.push(2);
+        buf.push(3);
+        buf.push_front(1);
+        assert_eq!(
+            buf.pop_front(),
+            Some(1),
+            "Expected to pop 1 from the front!"
+        );
+        buf.push(4);
+        buf.push(5);
+        buf.push_front(1);
+        assert!(buf.is_full(), "Expected the RingBufferBuilder to be full!");
+
+        assert_eq!(
+            buf.iter().copied().collect::<Vec<_>>(),
+            vec![1, 2, 3, 4, 5],
+            "The iterator should yield the items from front to back!"
+        );
+        assert_eq!(buf.get(2), Some(&3), "Expected 3 at logical index 2!");
+        assert_eq!(
+            buf.get_front_ref(),
+            &Some(1),
+            "The front should be Some(1)!"
+        );
+        assert_eq!(buf.get_back_ref(), &Some(5), "The back should be Some(5)!");
+
+        assert_eq!(buf.pop(), Some(5), "Expected to pop 5 from the back!");
+        while buf.pop_front().is_some() {}
+        assert_eq!(
+            buf.get_back_ref(),
+            &None,
+            "There should be None in the back after popping every item!"
+        );
+        assert_eq!(
+            buf.iter().next(),
+            None,
+            "Nothing should be left to iterate!"
+        );
+    }
+}
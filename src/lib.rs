@@ -1,3 +1,25 @@
+mod builder;
+
+pub use builder::RingBufferBuilder;
+
+use std::collections::{HashMap, VecDeque};
+use std::error::Error;
+use std::fmt;
+use std::hash::Hash;
+use std::io::{self, Read, Write};
+use std::iter::FusedIterator;
+use std::ops::{Add, Range};
+use std::panic::{RefUnwindSafe, UnwindSafe};
+use std::slice;
+
+/// A fixed-size double-ended ring buffer.
+///
+/// One slot of the array is always kept free to tell a full buffer from an empty one,
+/// so a `RingBuffer<T, CAPACITY>` holds at most `CAPACITY - 1` elements.
+/// Declare `CAPACITY` as `N + 1` to store `N` elements.
+///
+/// Every buffer also carries one pointer-sized slot for the optional overflow callback
+/// registered through `on_overflow`.
 #[derive(Default)]
 pub struct RingBuffer<T, const CAPACITY: usize>
 where
@@ -7,8 +29,11 @@ where
     data: [Option<T>; CAPACITY],
     front: usize,
     back: usize,
+    on_overflow: Option<OverflowCallback<T>>,
 }
 
+type OverflowCallback<T> = Box<dyn FnMut(&T) + Send + Sync + UnwindSafe + RefUnwindSafe>;
+
 impl<T, const CAPACITY: usize> RingBuffer<T, CAPACITY>
 where
     [Option<T>; CAPACITY]: Default,
@@ -18,8 +43,36 @@ where
         Self::default()
     }
 
+    /// Collects `iter` into a new buffer, returning the first `capacity()` items as an error
+    /// if `iter` yields more than fit.
+    pub fn try_from_iter(iter: impl IntoIterator<Item = T>) -> Result<Self, Self> {
+        let mut buf = Self::new();
+        for item in iter {
+            if !buf.push(item) {
+                return Err(buf);
+            }
+        }
+        Ok(buf)
+    }
+
+    /// Appends `b` after `a` in a new buffer, or returns `None` if they do not fit together.
+    pub fn concat<const A: usize, const B: usize>(
+        a: RingBuffer<T, A>,
+        b: RingBuffer<T, B>,
+    ) -> Option<Self>
+    where
+        [Option<T>; A]: Default,
+        [Option<T>; B]: Default,
+    {
+        if a.len() + b.len() > CAPACITY - 1 {
+            return None;
+        }
+        Self::try_from_iter(a.into_iter().chain(b)).ok()
+    }
+
     pub fn push(&mut self, item: T) -> bool {
         if self.is_full() {
+            self.overflow(&item);
             return false;
         }
         self.back = (self.back + 1) % CAPACITY;
@@ -27,19 +80,94 @@ where
         true
     }
 
+    /// Pushes items from `iter` until the buffer is full, returning how many were stored.
+    /// No item is pulled from `iter` once the buffer is full.
+    pub fn push_iter(&mut self, iter: impl IntoIterator<Item = T>) -> usize {
+        let mut iter = iter.into_iter();
+        let mut pushed = 0;
+        while !self.is_full() {
+            let Some(item) = iter.next() else {
+                break;
+            };
+            self.push(item);
+            pushed += 1;
+        }
+        pushed
+    }
+
+    /// Pushes `item` at the back, evicting and returning the front element if the buffer is full.
+    pub fn push_overwrite(&mut self, item: T) -> Option<T> {
+        let evicted = if self.is_full() {
+            self.pop_front()
+        } else {
+            None
+        };
+        self.push(item);
+        evicted
+    }
+
+    /// Like `push_overwrite`, but hands the evicted element to `on_evict` instead of returning it.
+    pub fn push_overwrite_with<F: FnMut(T)>(&mut self, item: T, mut on_evict: F) {
+        if let Some(evicted) = self.push_overwrite(item) {
+            on_evict(evicted);
+        }
+    }
+
+    /// Shifts `value` into the back of a delay line, returning the sample shifted out of the front
+    /// once the line is full. This is `push_overwrite` under the name DSP code expects.
+    ///
+    /// ```
+    /// use ring_buffer::RingBuffer;
+    ///
+    /// // A 3-tap moving-sum FIR filter, with the tap line primed with zeros.
+    /// let mut taps = RingBuffer::<i32, 4>::new();
+    /// for _ in 0..3 {
+    ///     taps.push(0);
+    /// }
+    ///
+    /// let output: Vec<i32> = [1, 2, 3, 4]
+    ///     .into_iter()
+    ///     .map(|sample| {
+    ///         taps.shift_in(sample);
+    ///         taps.iter().sum()
+    ///     })
+    ///     .collect();
+    /// assert_eq!(output, [1, 3, 6, 9]);
+    /// ```
+    pub fn shift_in(&mut self, value: T) -> Option<T> {
+        self.push_overwrite(value)
+    }
+
     pub fn push_front(&mut self, item: T) -> bool {
         if self.is_full() {
+            self.overflow(&item);
             return false;
         }
+        self.data[self.front] = Some(item);
         self.front = if self.front == 0 {
             CAPACITY - 1
         } else {
             self.front - 1
         };
-        self.data[self.front] = Some(item);
         true
     }
 
+    /// Registers `cb` to be called with every item `push` or `push_front` rejects because the buffer is full,
+    /// replacing any earlier callback. The rejected items are still dropped.
+    ///
+    /// The callback must be `Send + Sync + UnwindSafe + RefUnwindSafe` so the buffer keeps those
+    /// auto traits. Count through an `Arc<AtomicUsize>` or similar rather than an `Rc<Cell<_>>`,
+    /// which will not compile here.
+    pub fn on_overflow(&mut self, cb: OverflowCallback<T>) {
+        self.on_overflow = Some(cb);
+    }
+
+    fn overflow(&mut self, item: &T) {
+        if let Some(cb) = &mut self.on_overflow {
+            cb(item);
+        }
+    }
+
     pub fn pop(&mut self) -> Option<T> {
         if self.is_empty() {
             return None;
@@ -57,154 +185,3576 @@ where
         if self.is_empty() {
             return None;
         }
-        let item = self.data[self.front].take();
         self.front = (self.front + 1) % CAPACITY;
-        item
+        self.data[self.front].take()
     }
 
-    pub fn get_front_ref(&self) -> &Option<T> {
-        &self.data[self.front]
+    pub fn evict_front_while<F: FnMut(&T) -> bool>(&mut self, mut should_evict: F) -> usize {
+        let mut evicted = 0;
+        while let Some(item) = self.get_front_ref() {
+            if !should_evict(item) {
+                break;
+            }
+            self.pop_front();
+            evicted += 1;
+        }
+        evicted
     }
 
-    pub fn get_back_ref(&self) -> &Option<T> {
-        &self.data[self.back]
+    /// Overwrites the front element with `value` and returns the old one,
+    /// or returns `None` without storing `value` if the buffer is empty.
+    pub fn replace_front(&mut self, value: T) -> Option<T> {
+        self.replace(0, value)
     }
 
-    pub fn is_empty(&self) -> bool {
-        self.front == self.back
+    /// Overwrites the back element with `value` and returns the old one,
+    /// or returns `None` without storing `value` if the buffer is empty.
+    pub fn replace_back(&mut self, value: T) -> Option<T> {
+        let last = self.len().checked_sub(1)?;
+        self.replace(last, value)
     }
 
-    pub fn is_full(&self) -> bool {
-        (self.back + 1) % CAPACITY == self.front
+    /// Sets the element at the logical `index` (0 = front) and returns the previous one,
+    /// or `None` without storing anything if `index` is out of range.
+    pub fn replace(&mut self, index: usize, value: T) -> Option<T> {
+        if index >= self.len() {
+            return None;
+        }
+        let slot = self.physical_index(index);
+        self.data[slot].replace(value)
     }
-}
 
-impl<T, const CAPACITY: usize> RingBuffer<T, CAPACITY>
-where
-    [Option<T>; CAPACITY]: Default,
-    T: Default + Copy,
-{
-    pub fn get_front(&self) -> Option<T> {
-        self.data[self.front]
+    /// Returns the element at the logical `index`, where 0 is the front.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.len() {
+            return None;
+        }
+        self.data[self.physical_index(index)].as_ref()
     }
 
-    pub fn get_back(&self) -> Option<T> {
-        self.data[self.back]
+    /// Returns a mutable reference to the element at the logical index `n`, the mutable counterpart of `get`.
+    pub fn peek_nth_mut(&mut self, n: usize) -> Option<&mut T> {
+        if n >= self.len() {
+            return None;
+        }
+        let slot = self.physical_index(n);
+        self.data[slot].as_mut()
     }
-}
 
-// TODO
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Returns mutable references to the elements at the logical indices `i` and `j`,
+    /// or `None` if either is out of range or both are the same.
+    pub fn get_pair_mut(&mut self, i: usize, j: usize) -> Option<(&mut T, &mut T)> {
+        if i == j || i >= self.len() || j >= self.len() {
+            return None;
+        }
+        let (a, b) = (self.physical_index(i), self.physical_index(j));
+        if a < b {
+            let (low, high) = self.data.split_at_mut(b);
+            Some((low[a].as_mut()?, high[0].as_mut()?))
+        } else {
+            let (low, high) = self.data.split_at_mut(a);
+            Some((high[0].as_mut()?, low[b].as_mut()?))
+        }
+    }
 
-    #[test]
-    fn test_init() {
-        let buf = RingBuffer::<usize, 10>::new();
+    /// Returns the element `n` positions from the back, where 0 is the back.
+    pub fn nth_from_back(&self, n: usize) -> Option<&T> {
+        self.get(self.len().checked_sub(n)?.checked_sub(1)?)
+    }
 
-        assert_eq!(
-            buf.get_front(),
-            None,
-            "There should be None in the front directly after init!"
-        );
-        assert_eq!(
-            buf.get_back(),
-            None,
-            "There should be None in the back directly after init!"
-        );
+    /// Iterates from front to back, so the n-th item yielded is the one `get(n)` returns.
+    pub fn iter(&self) -> Iter<'_, T> {
+        let (head, tail) = self.as_slices();
+        Iter {
+            head: head.iter(),
+            tail: tail.iter(),
+        }
+    }
 
-        assert!(
-            buf.is_empty(),
-            "RingBuffer should be empty directly after init!"
-        );
-        assert!(
-            !buf.is_full(),
-            "RingBuffer should not be full directly after init!"
-        );
+    /// Pairs every element with its logical index, where 0 is the front.
+    pub fn iter_indexed(&self) -> impl Iterator<Item = (usize, &T)> {
+        self.iter().enumerate()
+    }
 
-        let string_buf = RingBuffer::<String, 10>::new();
+    /// Pairs every element, from front to back, with the index of the array slot holding it.
+    pub fn iter_with_slot(&self) -> impl Iterator<Item = (usize, &T)> {
+        self.iter()
+            .enumerate()
+            .map(|(i, item)| (self.physical_index(i), item))
+    }
 
-        assert_eq!(
-            string_buf.get_front_ref(),
-            &None,
-            "There should be &None (when the T is not copy) in the front directly after init!"
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        let (head, tail) = self.as_mut_slices();
+        IterMut {
+            head: head.iter_mut(),
+            tail: tail.iter_mut(),
+        }
+    }
+
+    /// Applies `f` to every element from front to back.
+    pub fn apply<F: FnMut(&mut T)>(&mut self, f: F) {
+        self.iter_mut().for_each(f);
+    }
+
+    /// Yields mutable references to the first `min(n, len())` elements from the front.
+    pub fn front_n_mut(&mut self, n: usize) -> impl Iterator<Item = &mut T> {
+        self.iter_mut().take(n)
+    }
+
+    /// Calls `f` on every overlapping window of `size` consecutive elements, from the front,
+    /// one after another so each window sees the edits made through the previous ones.
+    /// The elements are moved into a temporary `Vec` for this, since the slots cannot be borrowed as `&mut [T]`,
+    /// so every call allocates. They are moved back even if `f` panics.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    pub fn for_each_window_mut<F: FnMut(&mut [T])>(&mut self, size: usize, mut f: F) {
+        assert!(size != 0, "window size must not be zero");
+        let items = self.drain().collect::<Vec<_>>();
+        let mut guard = WindowGuard { buf: self, items };
+        for start in 0..guard.items.len().saturating_sub(size - 1) {
+            f(&mut guard.items[start..start + size]);
+        }
+    }
+
+    /// Pops every element from the front and hands it to `f`, leaving the buffer empty.
+    pub fn consume_with<F: FnMut(T)>(&mut self, f: F) {
+        self.drain().for_each(f);
+    }
+
+    /// Removes every element from the front, including those the returned iterator is not driven to.
+    pub fn drain(&mut self) -> Drain<'_, T, CAPACITY> {
+        Drain { buf: self }
+    }
+
+    /// Calls `f` on each element from front to back, stopping at the first `Err`.
+    pub fn try_for_each<E, F: FnMut(&T) -> Result<(), E>>(&self, f: F) -> Result<(), E> {
+        self.iter().try_for_each(f)
+    }
+
+    /// Clones the elements matching `pred` into the first buffer and the rest into the second, keeping their order.
+    pub fn partition<F: FnMut(&T) -> bool>(&self, mut pred: F) -> (Self, Self)
+    where
+        T: Clone,
+    {
+        let mut matching = Self::new();
+        let mut rest = Self::new();
+        for item in self.iter() {
+            if pred(item) {
+                matching.push(item.clone());
+            } else {
+                rest.push(item.clone());
+            }
+        }
+        (matching, rest)
+    }
+
+    /// Returns the logical index of the first element matching `pred`.
+    pub fn position<F: FnMut(&T) -> bool>(&self, pred: F) -> Option<usize> {
+        self.iter().position(pred)
+    }
+
+    /// Returns the logical index of the last element matching `pred`, scanning from the back.
+    pub fn rposition<F: FnMut(&T) -> bool>(&self, pred: F) -> Option<usize> {
+        self.iter().rposition(pred)
+    }
+
+    /// Returns the first `Some` produced by applying `f` to the elements from front to back.
+    pub fn find_map<U, F: FnMut(&T) -> Option<U>>(&self, f: F) -> Option<U> {
+        self.iter().find_map(f)
+    }
+
+    /// Combines the elements pairwise by logical index, or returns `None` if the lengths differ.
+    pub fn zip_with<U: Default, V: Default, F: FnMut(&T, &U) -> V>(
+        &self,
+        other: &RingBuffer<U, CAPACITY>,
+        mut f: F,
+    ) -> Option<RingBuffer<V, CAPACITY>>
+    where
+        [Option<U>; CAPACITY]: Default,
+        [Option<V>; CAPACITY]: Default,
+    {
+        if self.len() != other.len() {
+            return None;
+        }
+        Some(
+            self.iter()
+                .zip(other.iter())
+                .map(|(a, b)| f(a, b))
+                .collect(),
         )
     }
 
-    #[test]
-    fn test_push() {
-        const CAP: usize = 10;
-        let mut buf = RingBuffer::<usize, CAP>::new();
+    /// Pops and yields front elements while `pred` holds. The first element failing `pred` stays at the front.
+    ///
+    /// Elements are only removed as they are yielded. Unlike `drain()`, dropping the iterator early
+    /// leaves the matching elements it has not reached in the buffer.
+    pub fn drain_while<'a, F: FnMut(&T) -> bool + 'a>(
+        &'a mut self,
+        mut pred: F,
+    ) -> impl Iterator<Item = T> + 'a {
+        let mut done = false;
+        std::iter::from_fn(move || {
+            if done || !self.get_front_ref().as_ref().is_some_and(&mut pred) {
+                done = true;
+                return None;
+            }
+            self.pop_front()
+        })
+    }
 
-        for i in 1..CAP {
-            assert!(
-                buf.push(i),
-                "Expected to be able to fill the RingBuffer until the CAPACITY is reached!"
-            );
+    /// Maps the elements from front to back into a new buffer, threading `init` through every call of `f`.
+    pub fn scan<S, U: Default, F: FnMut(&mut S, &T) -> U>(
+        &self,
+        init: S,
+        mut f: F,
+    ) -> RingBuffer<U, CAPACITY>
+    where
+        [Option<U>; CAPACITY]: Default,
+    {
+        let mut state = init;
+        self.iter().map(|item| f(&mut state, item)).collect()
+    }
+
+    /// Whether the elements are in non-decreasing order from front to back.
+    pub fn is_sorted(&self) -> bool
+    where
+        T: PartialOrd,
+    {
+        self.iter().is_sorted()
+    }
+
+    /// Inserts `value` after every element not greater than it, assuming the buffer is sorted,
+    /// and returns its logical index. Hands `value` back if the buffer is full.
+    pub fn insert_sorted(&mut self, value: T) -> Result<usize, CapacityError<T>>
+    where
+        T: Ord,
+    {
+        if self.is_full() {
+            return Err(CapacityError(value));
         }
+        let index = self.partition_point(|item| *item <= value);
+        self.push(value);
+        for i in (index..self.len() - 1).rev() {
+            self.swap_logical(i, i + 1);
+        }
+        Ok(index)
+    }
 
-        assert!(!buf.push(CAP + 1), "Expected the RingBuffer to be full!");
+    /// Returns the logical index of the first element for which `pred` is false,
+    /// assuming every element satisfying `pred` comes before every element that does not.
+    pub fn partition_point<F: FnMut(&T) -> bool>(&self, mut pred: F) -> usize {
+        let (mut low, mut high) = (0, self.len());
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if self.get(mid).is_some_and(&mut pred) {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        low
     }
 
-    #[test]
-    fn test_push_front() {
-        const CAP: usize = 10;
-        let mut buf = RingBuffer::<usize, CAP>::new();
+    /// Rotates the first element matching `pred` to the front by moving the elements before it
+    /// to the back. Returns false and leaves the buffer as it is if nothing matches.
+    ///
+    /// This takes O(index) moves, not O(1). The free slot that tells a full buffer from an empty one
+    /// must stay between back and front, so the rotation cannot be done by adjusting the indices alone.
+    pub fn rotate_until<F: FnMut(&T) -> bool>(&mut self, pred: F) -> bool {
+        let Some(index) = self.position(pred) else {
+            return false;
+        };
+        for _ in 0..index {
+            if let Some(item) = self.pop_front() {
+                self.push(item);
+            }
+        }
+        true
+    }
 
-        for i in 1..CAP {
-            assert!(
-                buf.push_front(i),
-                "Expected to be able to fill the RingBuffer until the CAPACITY is reached!"
-            );
+    /// Moves the element at the logical `index` to the back, shifting the later elements forward.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index >= len()`.
+    pub fn move_to_back(&mut self, index: usize) {
+        assert!(index < self.len(), "index out of bounds");
+        for i in index..self.len() - 1 {
+            self.swap_logical(i, i + 1);
+        }
+    }
+
+    /// Moves the element at the logical `index` to the front, shifting the earlier elements back.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index >= len()`.
+    pub fn move_to_front(&mut self, index: usize) {
+        assert!(index < self.len(), "index out of bounds");
+        for i in (0..index).rev() {
+            self.swap_logical(i, i + 1);
         }
+    }
+
+    /// Whether any element equals any of `values`, stopping at the first match.
+    pub fn contains_any(&self, values: &[T]) -> bool
+    where
+        T: PartialEq,
+    {
+        self.iter().any(|item| values.contains(item))
+    }
 
+    /// Returns the logical index where `needle` first appears as a run of consecutive elements.
+    pub fn contains_subsequence(&self, needle: &[T]) -> Option<usize>
+    where
+        T: PartialEq,
+    {
+        let last_start = self.len().checked_sub(needle.len())?;
+        (0..=last_start).find(|&start| {
+            needle
+                .iter()
+                .enumerate()
+                .all(|(offset, item)| self.get(start + offset) == Some(item))
+        })
+    }
+
+    /// Yields the elements from front to back over and over, never ending unless the buffer is empty.
+    pub fn iter_cycle(&self) -> impl Iterator<Item = &T> {
+        self.iter().cycle()
+    }
+
+    /// Yields up to `n` of the newest elements, oldest first.
+    pub fn last_n(&self, n: usize) -> impl Iterator<Item = &T> {
+        self.iter().skip(self.len().saturating_sub(n))
+    }
+
+    /// Yields up to `n` of the newest elements, newest first.
+    pub fn back_n(&self, n: usize) -> impl Iterator<Item = &T> {
+        self.iter().rev().take(n)
+    }
+
+    /// Yields every `step`-th element, starting with the front.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step` is 0.
+    pub fn stride(&self, step: usize) -> impl Iterator<Item = &T> {
+        assert!(step != 0, "stride step must not be zero");
+        self.iter().step_by(step)
+    }
+
+    /// Returns the front element and an iterator over the rest.
+    pub fn split_first(&self) -> Option<(&T, impl Iterator<Item = &T>)> {
+        let mut rest = self.iter();
+        let first = rest.next()?;
+        Some((first, rest))
+    }
+
+    /// Returns the back element and an iterator over the rest, from the front.
+    pub fn split_last(&self) -> Option<(&T, impl Iterator<Item = &T>)> {
+        let mut rest = self.iter();
+        let last = rest.next_back()?;
+        Some((last, rest))
+    }
+
+    /// Yields non-overlapping chunks of `size` elements starting from the back,
+    /// each in front-to-back order, with the oldest chunk possibly shorter.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    pub fn rchunks(&self, size: usize) -> impl Iterator<Item = Vec<&T>> {
+        assert!(size != 0, "chunk size must not be zero");
+        let mut end = self.len();
+        std::iter::from_fn(move || {
+            if end == 0 {
+                return None;
+            }
+            let start = end.saturating_sub(size);
+            let chunk = self.iter().skip(start).take(end - start).collect();
+            end = start;
+            Some(chunk)
+        })
+    }
+
+    /// Returns references to the elements in the logical `range`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range starts after it ends or ends after `len()`.
+    pub fn slice(&self, range: Range<usize>) -> Vec<&T> {
         assert!(
-            !buf.push_front(CAP + 1),
-            "Expected the RingBuffer to be full!"
+            range.start <= range.end && range.end <= self.len(),
+            "slice range out of bounds"
         );
+        self.iter()
+            .skip(range.start)
+            .take(range.end - range.start)
+            .collect()
     }
 
-    #[test]
-    fn test_pop() {
-        let mut buf = RingBuffer::<String, 10>::new();
+    /// Splits the elements into the logical ranges `[0, index)` and `[index, len())`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len()`.
+    pub fn split_at(&self, index: usize) -> (impl Iterator<Item = &T>, impl Iterator<Item = &T>) {
+        assert!(index <= self.len(), "split index out of bounds");
+        (self.iter().take(index), self.iter().skip(index))
+    }
 
-        assert_eq!(
-            buf.pop(),
-            None,
-            "The last item should be empty directly after init!"
-        );
+    /// Returns the element with the largest key, or the last of them if several are equally large.
+    pub fn max_by_key<K: Ord, F: FnMut(&T) -> K>(&self, mut f: F) -> Option<&T> {
+        self.iter().max_by_key(|item| f(item))
+    }
 
-        buf.push("Test".to_string());
-        assert_eq!(
-            buf.pop(),
-            Some("Test".to_string()),
-            "The last item should be Some(\"Test\") directly after pushing \"Test\"!"
-        );
+    /// Returns the element with the smallest key, or the first of them if several are equally small.
+    pub fn min_by_key<K: Ord, F: FnMut(&T) -> K>(&self, mut f: F) -> Option<&T> {
+        self.iter().min_by_key(|item| f(item))
+    }
+
+    /// Returns the running sum at each logical position.
+    pub fn prefix_sums(&self) -> Vec<T>
+    where
+        T: Add<Output = T> + Copy,
+    {
+        self.iter()
+            .scan(T::default(), |sum, &item| {
+                *sum = *sum + item;
+                Some(*sum)
+            })
+            .collect()
+    }
+
+    /// The arithmetic mean of the elements, or `None` if the buffer is empty.
+    ///
+    /// Walks the two contiguous `Option<T>` segments rather than indexing the ring per
+    /// element, but the `Option` wrappers still keep the loop from being vectorized.
+    pub fn mean(&self) -> Option<f64>
+    where
+        T: Into<f64> + Copy,
+    {
+        if self.is_empty() {
+            return None;
+        }
+        let sum: f64 = self.iter().map(|&item| item.into()).sum();
+        Some(sum / self.len() as f64)
+    }
+
+    /// Concatenates the items of every element, from front to back, into one `Vec`.
+    pub fn flatten<U>(&self) -> Vec<U>
+    where
+        T: IntoIterator<Item = U> + Clone,
+        U: Clone,
+    {
+        self.iter().cloned().flatten().collect()
+    }
+
+    /// Groups runs of consecutive elements that map to the same key.
+    pub fn group_consecutive<K: PartialEq, F: FnMut(&T) -> K>(&self, mut key: F) -> Vec<Vec<&T>> {
+        let mut groups: Vec<Vec<&T>> = Vec::new();
+        let mut last_key = None;
+        for item in self.iter() {
+            let item_key = key(item);
+            match groups.last_mut() {
+                Some(group) if last_key.as_ref() == Some(&item_key) => group.push(item),
+                _ => groups.push(vec![item]),
+            }
+            last_key = Some(item_key);
+        }
+        groups
+    }
+
+    /// Splits the elements at every one matching `pred`, dropping the delimiters.
+    /// Like `slice::split`, adjacent delimiters produce empty segments.
+    pub fn split_when<F: FnMut(&T) -> bool>(&self, mut pred: F) -> Vec<Self>
+    where
+        T: Clone,
+    {
+        let mut segments = vec![Self::new()];
+        for item in self.iter() {
+            if pred(item) {
+                segments.push(Self::new());
+            } else if let Some(segment) = segments.last_mut() {
+                segment.push(item.clone());
+            }
+        }
+        segments
+    }
+
+    /// Clones the current elements from front to back into a `Vec`.
+    ///
+    /// This is the snapshot primitive for readers: the copy is taken in one call, so it never
+    /// mixes states from before and after a later push, pop or rotation. A future
+    /// single-producer/single-consumer split is meant to build its consumer-side snapshot on it.
+    pub fn snapshot(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        self.iter().cloned().collect()
+    }
+
+    /// Clones the elements in order into a `VecDeque` with room for `capacity()` elements.
+    pub fn to_vec_deque(&self) -> VecDeque<T>
+    where
+        T: Clone,
+    {
+        let mut deque = VecDeque::with_capacity(self.capacity());
+        deque.extend(self.iter().cloned());
+        deque
+    }
+
+    /// Counts how many times each distinct element occurs.
+    pub fn counts(&self) -> HashMap<T, usize>
+    where
+        T: Eq + Hash + Clone,
+    {
+        let mut counts = HashMap::new();
+        for item in self.iter() {
+            *counts.entry(item.clone()).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Removes the elements in the logical `range` and yields them in order,
+    /// closing the gap by moving the later elements forward.
+    ///
+    /// Unlike `Vec::drain`, the removal is eager: the range is moved into a freshly
+    /// allocated `Vec` before this returns, so dropping the iterator early still removes
+    /// the whole range. Use `clear_range` to drop the elements without allocating.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range starts after it ends or ends after `len()`.
+    pub fn drain_range(&mut self, range: Range<usize>) -> impl Iterator<Item = T> + '_ {
+        let len = self.len();
         assert!(
-            buf.is_empty(),
-            "The RingBuffer should be empty after removing the only item!"
+            range.start <= range.end && range.end <= len,
+            "drain range out of bounds"
         );
+        let removed = range
+            .clone()
+            .filter_map(|i| self.data[self.physical_index(i)].take())
+            .collect::<Vec<_>>();
+        self.close_gap(range, len);
+        removed.into_iter()
     }
 
-    #[test]
-    fn test_pop_front() {
-        let mut buf = RingBuffer::<usize, 10>::new();
+    /// Moves the elements after the already emptied logical `range` forward over it
+    /// and shrinks the buffer by `range.len()`. `len` is the length before the removal.
+    fn close_gap(&mut self, range: Range<usize>, len: usize) {
+        let count = range.len();
+        for i in range.end..len {
+            let (from, to) = (self.physical_index(i), self.physical_index(i - count));
+            self.data[to] = self.data[from].take();
+        }
+        self.back = (self.back + CAPACITY - count) % CAPACITY;
+    }
 
-        assert_eq!(
-            buf.pop_front(),
-            None,
-            "The last item should be empty directly after init!"
-        );
+    /// Whether both buffers hold the same elements with the same multiplicities, in any order.
+    pub fn eq_ignore_order(&self, other: &Self) -> bool
+    where
+        T: Eq + Hash,
+    {
+        if self.len() != other.len() {
+            return false;
+        }
+        let mut counts = HashMap::new();
+        for item in self.iter() {
+            *counts.entry(item).or_insert(0) += 1;
+        }
+        for item in other.iter() {
+            match counts.get_mut(item) {
+                Some(count) if *count > 0 => *count -= 1,
+                _ => return false,
+            }
+        }
+        true
+    }
 
-        buf.push_front(1);
-        assert_eq!(
-            buf.pop_front(),
-            Some(1),
-            "The last item should be Some(1) directly after pushing 1!"
-        );
+    /// Drops the elements in the logical `range` in place, closing the gap like `drain_range`
+    /// but without collecting them first.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range starts after it ends or ends after `len()`.
+    pub fn clear_range(&mut self, range: Range<usize>) {
+        let len = self.len();
         assert!(
-            buf.is_empty(),
-            "The RingBuffer should be empty after removing the only item!"
+            range.start <= range.end && range.end <= len,
+            "clear range out of bounds"
         );
+        for i in range.clone() {
+            let index = self.physical_index(i);
+            self.data[index] = None;
+        }
+        self.close_gap(range, len);
+    }
+
+    /// Keeps only the elements for which `f` returns true, given each element's logical index,
+    /// closing the gaps left by the dropped ones while keeping the order.
+    pub fn retain_indexed<F: FnMut(usize, &T) -> bool>(&mut self, mut f: F) {
+        let len = self.len();
+        let mut guard = RetainGuard {
+            buf: self,
+            len,
+            processed: 0,
+            kept: 0,
+        };
+        while guard.processed < guard.len {
+            let i = guard.processed;
+            let slot = guard.buf.physical_index(i);
+            if guard.buf.data[slot].as_ref().is_some_and(|item| f(i, item)) {
+                let to = guard.buf.physical_index(guard.kept);
+                if to != slot {
+                    guard.buf.data[to] = guard.buf.data[slot].take();
+                }
+                guard.kept += 1;
+            } else {
+                guard.buf.data[slot] = None;
+            }
+            guard.processed += 1;
+        }
+    }
+
+    /// Shrinks the buffer to at most `len` elements, dropping the excess from the end that is not kept.
+    pub fn compact_to(&mut self, len: usize, keep: Keep) {
+        while self.len() > len {
+            match keep {
+                Keep::Newest => self.pop_front(),
+                Keep::Oldest => self.pop(),
+            };
+        }
+    }
+
+    /// Moves the elements into an array from front to back, or returns the buffer unchanged if `len() != N`.
+    pub fn into_array<const N: usize>(self) -> Result<[T; N], Self> {
+        if self.len() != N {
+            return Err(self);
+        }
+        let mut items = self.into_iter();
+        Ok(std::array::from_fn(|_| items.next().unwrap_or_default()))
+    }
+
+    /// Removes the oldest elements until at most `len` remain and returns them in order.
+    pub fn trim_to(&mut self, len: usize) -> Vec<T> {
+        let excess = self.len().saturating_sub(len);
+        (0..excess).filter_map(|_| self.pop_front()).collect()
+    }
+
+    /// Grows the buffer to `new_len` with values from `f`, capped at `capacity()`,
+    /// or shrinks it by dropping elements from the back.
+    pub fn resize_with<F: FnMut() -> T>(&mut self, new_len: usize, mut f: F) {
+        self.compact_to(new_len, Keep::Oldest);
+        while self.len() < new_len && !self.is_full() {
+            self.push(f());
+        }
+    }
+
+    /// Moves the indices of an empty buffer back to the start of the array.
+    /// A non-empty buffer is left alone.
+    pub fn normalize(&mut self) {
+        if self.is_empty() {
+            self.front = 0;
+            self.back = 0;
+        }
+    }
+
+    pub fn clear(&mut self) {
+        for slot in &mut self.data {
+            *slot = None;
+        }
+        self.front = 0;
+        self.back = 0;
+    }
+
+    pub fn get_front_ref(&self) -> &Option<T> {
+        if self.is_empty() {
+            return &None;
+        }
+        &self.data[self.physical_index(0)]
+    }
+
+    pub fn get_back_ref(&self) -> &Option<T> {
+        if self.is_empty() {
+            return &None;
+        }
+        &self.data[self.back]
+    }
+
+    pub fn try_front(&self) -> Result<&T, EmptyError> {
+        self.get_front_ref().as_ref().ok_or(EmptyError)
+    }
+
+    pub fn try_back(&self) -> Result<&T, EmptyError> {
+        self.get_back_ref().as_ref().ok_or(EmptyError)
+    }
+
+    /// Returns the front and back elements together, which are the same element when there is only one.
+    pub fn ends(&self) -> Option<(&T, &T)> {
+        Some((
+            self.get_front_ref().as_ref()?,
+            self.get_back_ref().as_ref()?,
+        ))
+    }
+
+    /// Returns mutable references to the front and back elements, or `None` with fewer than two elements.
+    pub fn ends_mut(&mut self) -> Option<(&mut T, &mut T)> {
+        let last = self.len().checked_sub(1)?;
+        self.get_pair_mut(0, last)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.front == self.back
+    }
+
+    pub fn is_full(&self) -> bool {
+        (self.back + 1) % CAPACITY == self.front
+    }
+
+    pub fn len(&self) -> usize {
+        (self.back + CAPACITY - self.front) % CAPACITY
+    }
+
+    /// Whether the elements occupy one unbroken span of the underlying array.
+    pub fn is_contiguous(&self) -> bool {
+        self.as_slices().1.is_empty()
+    }
+
+    /// The number of elements the buffer can hold, which is one less than `CAPACITY`.
+    pub fn capacity(&self) -> usize {
+        CAPACITY - 1
+    }
+
+    pub fn remaining_capacity(&self) -> usize {
+        self.capacity() - self.len()
+    }
+
+    /// The share of `capacity()` in use, from 0.0 when empty to 1.0 when full.
+    /// A buffer with no usable capacity is always full, so it reports 1.0.
+    pub fn fill_ratio(&self) -> f64 {
+        if self.capacity() == 0 {
+            return 1.0;
+        }
+        self.len() as f64 / self.capacity() as f64
+    }
+
+    /// Mirrors `VecDeque::reserve` for a buffer that cannot grow, failing if `additional` more elements would not fit.
+    pub fn reserve(&self, additional: usize) -> Result<(), CapacityError<()>> {
+        if self.can_fit(additional) {
+            Ok(())
+        } else {
+            Err(CapacityError(()))
+        }
+    }
+
+    pub fn capacity_info(&self) -> CapacityInfo {
+        CapacityInfo {
+            raw_capacity: CAPACITY,
+            usable_capacity: self.capacity(),
+            len: self.len(),
+            remaining: self.remaining_capacity(),
+            wasted_slots: CAPACITY - self.capacity(),
+        }
+    }
+
+    /// Whether `additional` more elements would fit without anything being rejected.
+    pub fn can_fit(&self, additional: usize) -> bool {
+        additional <= self.remaining_capacity()
+    }
+
+    fn physical_index(&self, index: usize) -> usize {
+        (self.front + 1 + index) % CAPACITY
+    }
+
+    fn swap_logical(&mut self, a: usize, b: usize) {
+        let (a, b) = (self.physical_index(a), self.physical_index(b));
+        self.data.swap(a, b);
+    }
+
+    /// The occupied slots in logical order, split where they wrap around the array.
+    fn as_slices(&self) -> (&[Option<T>], &[Option<T>]) {
+        if self.is_empty() {
+            return (&[], &[]);
+        }
+        let first = self.physical_index(0);
+        if first <= self.back {
+            (&self.data[first..=self.back], &[])
+        } else {
+            (&self.data[first..], &self.data[..=self.back])
+        }
+    }
+
+    fn as_mut_slices(&mut self) -> (&mut [Option<T>], &mut [Option<T>]) {
+        if self.is_empty() {
+            return (&mut [], &mut []);
+        }
+        let first = self.physical_index(0);
+        if first <= self.back {
+            (&mut self.data[first..=self.back], &mut [])
+        } else {
+            let (wrapped, head) = self.data.split_at_mut(first);
+            (head, &mut wrapped[..=self.back])
+        }
+    }
+}
+
+impl<T, const CAPACITY: usize> RingBuffer<T, CAPACITY>
+where
+    [Option<T>; CAPACITY]: Default,
+    T: Default + Copy,
+{
+    pub fn get_front(&self) -> Option<T> {
+        *self.get_front_ref()
+    }
+
+    pub fn get_back(&self) -> Option<T> {
+        *self.get_back_ref()
+    }
+
+    pub fn front_or(&self, default: T) -> T {
+        self.get_front().unwrap_or(default)
+    }
+
+    /// Copies `N` consecutive elements starting at the logical index `start` into an array,
+    /// or returns `None` if they run past `len()`.
+    pub fn copy_range<const N: usize>(&self, start: usize) -> Option<[T; N]> {
+        if start.checked_add(N)? > self.len() {
+            return None;
+        }
+        Some(std::array::from_fn(|offset| {
+            self.get(start + offset).copied().unwrap_or_default()
+        }))
+    }
+
+    /// Copies the elements in the logical `src` range to start at the logical index `dest`,
+    /// with the same overlap handling as `slice::copy_within`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `src` starts after it ends, or if `src` or the destination reaches past `len()`.
+    pub fn copy_within(&mut self, src: Range<usize>, dest: usize) {
+        assert!(
+            src.start <= src.end && src.end <= self.len(),
+            "source range out of bounds"
+        );
+        let count = src.len();
+        assert!(dest <= self.len() - count, "destination out of bounds");
+        let copy = |buf: &mut Self, offset: usize| {
+            let from = buf.physical_index(src.start + offset);
+            let to = buf.physical_index(dest + offset);
+            buf.data[to] = buf.data[from];
+        };
+        if dest > src.start {
+            (0..count).rev().for_each(|offset| copy(self, offset));
+        } else {
+            (0..count).for_each(|offset| copy(self, offset));
+        }
+    }
+
+    /// Removes up to `n` elements from the front in O(1), returning how many were removed.
+    /// The vacated slots keep their stale copies until overwritten, which is harmless
+    /// for `Copy` elements since no accessor reads outside the occupied range.
+    pub fn drain_front(&mut self, n: usize) -> usize {
+        let count = n.min(self.len());
+        self.front = (self.front + count) % CAPACITY;
+        count
+    }
+
+    /// Pops up to `out.len()` elements from the front into `out`, returning how many were written.
+    /// The elements are copied out of the contiguous segments and `front` is then advanced once,
+    /// like `drain_front`.
+    pub fn pop_front_into(&mut self, out: &mut [T]) -> usize {
+        let mut count = 0;
+        for (slot, item) in out.iter_mut().zip(self.iter().copied()) {
+            *slot = item;
+            count += 1;
+        }
+        self.drain_front(count)
+    }
+}
+
+impl<const CAPACITY: usize> RingBuffer<u8, CAPACITY>
+where
+    [Option<u8>; CAPACITY]: Default,
+{
+    /// Reads from `r` until the buffer is full or `r` is exhausted, returning the number of bytes stored.
+    /// The slots hold `Option<u8>`, so reads go through a small stack chunk of at most
+    /// 16 bytes instead of straight into the storage.
+    pub fn fill_from_reader<R: Read>(&mut self, r: &mut R) -> io::Result<usize> {
+        const FILL_CHUNK: usize = 16;
+
+        let mut scratch = [0; FILL_CHUNK];
+        let mut stored = 0;
+        while !self.is_full() {
+            let wanted = self.remaining_capacity().min(FILL_CHUNK);
+            let read = match r.read(&mut scratch[..wanted]) {
+                Ok(0) => break,
+                Ok(read) => read,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            };
+            for &byte in &scratch[..read] {
+                self.push(byte);
+            }
+            stored += read;
+        }
+        Ok(stored)
+    }
+
+    /// Writes every buffered byte to `w` in order and empties the buffer, returning the number of bytes written.
+    /// The buffer is left untouched if writing fails.
+    pub fn drain_to_writer<W: Write>(&mut self, w: &mut W) -> io::Result<usize> {
+        let (head, tail) = self.as_slices();
+        let mut scratch = [0; CAPACITY];
+        let len = head.len() + tail.len();
+        for (byte, slot) in scratch.iter_mut().zip(head.iter().chain(tail)) {
+            *byte = slot.unwrap_or_default();
+        }
+        w.write_all(&scratch[..len])?;
+        self.clear();
+        Ok(len)
+    }
+}
+
+impl<T, const CAPACITY: usize> Clone for RingBuffer<T, CAPACITY>
+where
+    [Option<T>; CAPACITY]: Default,
+    T: Default + Clone,
+{
+    /// The overflow callback cannot be cloned, so the clone starts without one.
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data.clone(),
+            front: self.front,
+            back: self.back,
+            on_overflow: None,
+        }
+    }
+
+    /// Clones `source` slot by slot into the existing storage, so elements that are
+    /// present in both buffers can reuse their allocations through `T::clone_from`.
+    /// The destination keeps its own overflow callback. It is emptied before any
+    /// element is cloned, so a panicking `T::clone_from` leaves it empty rather
+    /// than half-copied.
+    fn clone_from(&mut self, source: &Self) {
+        self.front = source.front;
+        self.back = source.front;
+        for (slot, source_slot) in self.data.iter_mut().zip(&source.data) {
+            slot.clone_from(source_slot);
+        }
+        self.front = source.front;
+        self.back = source.back;
+    }
+}
+
+/// Collects up to `CAPACITY - 1` items, silently dropping the rest.
+impl<T, const CAPACITY: usize> FromIterator<T> for RingBuffer<T, CAPACITY>
+where
+    [Option<T>; CAPACITY]: Default,
+    T: Default,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut buf = Self::new();
+        for item in iter {
+            if !buf.push(item) {
+                break;
+            }
+        }
+        buf
+    }
+}
+
+/// Pushes items until the buffer is full, leaving the rest of the iterator unconsumed.
+impl<T, const CAPACITY: usize> Extend<T> for RingBuffer<T, CAPACITY>
+where
+    [Option<T>; CAPACITY]: Default,
+    T: Default,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.push_iter(iter);
+    }
+}
+
+impl<'a, T, const CAPACITY: usize> Extend<&'a T> for RingBuffer<T, CAPACITY>
+where
+    [Option<T>; CAPACITY]: Default,
+    T: Default + Copy,
+{
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.push_iter(iter.into_iter().copied());
+    }
+}
+
+/// A snapshot of how a buffer's slots are used, including the slot kept free to tell full from empty.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CapacityInfo {
+    /// The `CAPACITY` the buffer was declared with.
+    pub raw_capacity: usize,
+    /// How many elements the buffer can hold.
+    pub usable_capacity: usize,
+    pub len: usize,
+    pub remaining: usize,
+    /// Slots that can never hold an element.
+    pub wasted_slots: usize,
+}
+
+/// Which end of the buffer `compact_to` keeps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Keep {
+    Newest,
+    Oldest,
+}
+
+/// Returned when an element does not fit into a full buffer, handing the element back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CapacityError<T>(pub T);
+
+impl<T> CapacityError<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> fmt::Display for CapacityError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("the ring buffer is full")
+    }
+}
+
+impl<T: fmt::Debug> Error for CapacityError<T> {}
+
+/// Returned when an element is requested from an empty buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EmptyError;
+
+impl fmt::Display for EmptyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("the ring buffer is empty")
+    }
+}
+
+impl Error for EmptyError {}
+
+pub struct Iter<'a, T> {
+    head: slice::Iter<'a, Option<T>>,
+    tail: slice::Iter<'a, Option<T>>,
+}
+
+impl<T> Clone for Iter<'_, T> {
+    fn clone(&self) -> Self {
+        Self {
+            head: self.head.clone(),
+            tail: self.tail.clone(),
+        }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.head
+            .next()
+            .or_else(|| self.tail.next())
+            .and_then(Option::as_ref)
+    }
+
+    /// Skips within the two segments in O(1), so `skip` and `nth` don't walk every element.
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let head_len = self.head.len();
+        if n < head_len {
+            return self.head.nth(n).and_then(Option::as_ref);
+        }
+        self.head = [].iter();
+        self.tail.nth(n - head_len).and_then(Option::as_ref)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.head.len() + self.tail.len();
+        (len, Some(len))
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.tail
+            .next_back()
+            .or_else(|| self.head.next_back())
+            .and_then(Option::as_ref)
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T> FusedIterator for Iter<'_, T> {}
+
+pub struct IterMut<'a, T> {
+    head: slice::IterMut<'a, Option<T>>,
+    tail: slice::IterMut<'a, Option<T>>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.head
+            .next()
+            .or_else(|| self.tail.next())
+            .and_then(Option::as_mut)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.head.len() + self.tail.len();
+        (len, Some(len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IterMut<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.tail
+            .next_back()
+            .or_else(|| self.head.next_back())
+            .and_then(Option::as_mut)
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
+impl<T> FusedIterator for IterMut<'_, T> {}
+
+pub struct Drain<'a, T, const CAPACITY: usize>
+where
+    [Option<T>; CAPACITY]: Default,
+    T: Default,
+{
+    buf: &'a mut RingBuffer<T, CAPACITY>,
+}
+
+impl<T, const CAPACITY: usize> Iterator for Drain<'_, T, CAPACITY>
+where
+    [Option<T>; CAPACITY]: Default,
+    T: Default,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.buf.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.buf.len();
+        (len, Some(len))
+    }
+}
+
+impl<T, const CAPACITY: usize> FusedIterator for Drain<'_, T, CAPACITY>
+where
+    [Option<T>; CAPACITY]: Default,
+    T: Default,
+{
+}
+
+impl<T, const CAPACITY: usize> Drop for Drain<'_, T, CAPACITY>
+where
+    [Option<T>; CAPACITY]: Default,
+    T: Default,
+{
+    fn drop(&mut self) {
+        self.buf.clear();
+    }
+}
+
+/// Moves the elements taken out by `for_each_window_mut` back into the buffer when dropped,
+/// even if the callback panicked.
+struct WindowGuard<'a, T, const CAPACITY: usize>
+where
+    [Option<T>; CAPACITY]: Default,
+    T: Default,
+{
+    buf: &'a mut RingBuffer<T, CAPACITY>,
+    items: Vec<T>,
+}
+
+impl<T, const CAPACITY: usize> Drop for WindowGuard<'_, T, CAPACITY>
+where
+    [Option<T>; CAPACITY]: Default,
+    T: Default,
+{
+    fn drop(&mut self) {
+        self.buf.push_iter(self.items.drain(..));
+    }
+}
+
+/// Closes the gap left by `retain_indexed` when dropped, even if the predicate panicked,
+/// by shifting the elements it has not visited yet down to the kept ones.
+struct RetainGuard<'a, T, const CAPACITY: usize>
+where
+    [Option<T>; CAPACITY]: Default,
+    T: Default,
+{
+    buf: &'a mut RingBuffer<T, CAPACITY>,
+    len: usize,
+    processed: usize,
+    kept: usize,
+}
+
+impl<T, const CAPACITY: usize> Drop for RetainGuard<'_, T, CAPACITY>
+where
+    [Option<T>; CAPACITY]: Default,
+    T: Default,
+{
+    fn drop(&mut self) {
+        let removed = self.processed - self.kept;
+        if removed > 0 {
+            for i in self.processed..self.len {
+                let (from, to) = (
+                    self.buf.physical_index(i),
+                    self.buf.physical_index(i - removed),
+                );
+                self.buf.data[to] = self.buf.data[from].take();
+            }
+        }
+        self.buf.back = (self.buf.front + self.len - removed) % CAPACITY;
+    }
+}
+
+pub struct IntoIter<T, const CAPACITY: usize>
+where
+    [Option<T>; CAPACITY]: Default,
+    T: Default,
+{
+    buf: RingBuffer<T, CAPACITY>,
+}
+
+impl<T, const CAPACITY: usize> Iterator for IntoIter<T, CAPACITY>
+where
+    [Option<T>; CAPACITY]: Default,
+    T: Default,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.buf.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.buf.len();
+        (len, Some(len))
+    }
+}
+
+impl<T, const CAPACITY: usize> FusedIterator for IntoIter<T, CAPACITY>
+where
+    [Option<T>; CAPACITY]: Default,
+    T: Default,
+{
+}
+
+impl<T, const CAPACITY: usize> IntoIterator for RingBuffer<T, CAPACITY>
+where
+    [Option<T>; CAPACITY]: Default,
+    T: Default,
+{
+    type Item = T;
+    type IntoIter = IntoIter<T, CAPACITY>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { buf: self }
+    }
+}
+
+// TODO
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::panic::{self, AssertUnwindSafe};
+    use std::rc::{Rc, Weak};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    /// Returns a buffer holding `1..=5` whose elements wrap around the end of the array.
+    fn wrapped_buffer() -> RingBuffer<usize, 6> {
+        let mut buf = RingBuffer::<usize, 6>::new();
+        for _ in 0..3 {
+            buf.push(0);
+        }
+        for _ in 0..3 {
+            buf.pop_front();
+        }
+        for i in 1..=5 {
+            buf.push(i);
+        }
+        buf
+    }
+
+    #[test]
+    fn test_init() {
+        let buf = RingBuffer::<usize, 10>::new();
+
+        assert_eq!(
+            buf.get_front(),
+            None,
+            "There should be None in the front directly after init!"
+        );
+        assert_eq!(
+            buf.get_back(),
+            None,
+            "There should be None in the back directly after init!"
+        );
+
+        assert!(
+            buf.is_empty(),
+            "RingBuffer should be empty directly after init!"
+        );
+        assert!(
+            !buf.is_full(),
+            "RingBuffer should not be full directly after init!"
+        );
+
+        let string_buf = RingBuffer::<String, 10>::new();
+
+        assert_eq!(
+            string_buf.get_front_ref(),
+            &None,
+            "There should be &None (when the T is not copy) in the front directly after init!"
+        )
+    }
+
+    #[test]
+    fn test_push() {
+        const CAP: usize = 10;
+        let mut buf = RingBuffer::<usize, CAP>::new();
+
+        for i in 1..CAP {
+            assert!(
+                buf.push(i),
+                "Expected to be able to fill the RingBuffer until the CAPACITY is reached!"
+            );
+        }
+
+        assert!(!buf.push(CAP + 1), "Expected the RingBuffer to be full!");
+    }
+
+    #[test]
+    fn test_push_front() {
+        const CAP: usize = 10;
+        let mut buf = RingBuffer::<usize, CAP>::new();
+
+        for i in 1..CAP {
+            assert!(
+                buf.push_front(i),
+                "Expected to be able to fill the RingBuffer until the CAPACITY is reached!"
+            );
+        }
+
+        assert!(
+            !buf.push_front(CAP + 1),
+            "Expected the RingBuffer to be full!"
+        );
+    }
+
+    #[test]
+    fn test_pop() {
+        let mut buf = RingBuffer::<String, 10>::new();
+
+        assert_eq!(
+            buf.pop(),
+            None,
+            "The last item should be empty directly after init!"
+        );
+
+        buf.push("Test".to_string());
+        assert_eq!(
+            buf.pop(),
+            Some("Test".to_string()),
+            "The last item should be Some(\"Test\") directly after pushing \"Test\"!"
+        );
+        assert!(
+            buf.is_empty(),
+            "The RingBuffer should be empty after removing the only item!"
+        );
+    }
+
+    #[test]
+    fn test_pop_front() {
+        let mut buf = RingBuffer::<usize, 10>::new();
+
+        assert_eq!(
+            buf.pop_front(),
+            None,
+            "The last item should be empty directly after init!"
+        );
+
+        buf.push_front(1);
+        assert_eq!(
+            buf.pop_front(),
+            Some(1),
+            "The last item should be Some(1) directly after pushing 1!"
+        );
+        assert!(
+            buf.is_empty(),
+            "The RingBuffer should be empty after removing the only item!"
+        );
+    }
+
+    #[test]
+    fn test_push_then_pop_front() {
+        let mut buf = RingBuffer::<usize, 4>::new();
+
+        buf.push(1);
+        buf.push(2);
+        buf.push_front(0);
+        assert_eq!(buf.get_front(), Some(0), "The front should be Some(0)!");
+        assert_eq!(buf.get_back(), Some(2), "The back should be Some(2)!");
+
+        assert_eq!(
+            buf.pop_front(),
+            Some(0),
+            "Expected to pop 0 from the front!"
+        );
+        assert_eq!(
+            buf.pop_front(),
+            Some(1),
+            "Expected to pop 1 from the front!"
+        );
+        assert_eq!(buf.pop(), Some(2), "Expected to pop 2 from the back!");
+        assert!(
+            buf.is_empty(),
+            "The RingBuffer should be empty after removing every item!"
+        );
+    }
+
+    #[test]
+    fn test_evict_front_while() {
+        let mut buf = RingBuffer::<(usize, u64), 10>::new();
+
+        for (id, timestamp) in [(1, 100), (2, 150), (3, 200), (4, 250), (5, 300)] {
+            buf.push((id, timestamp));
+        }
+
+        let threshold = 200;
+        let evicted = buf.evict_front_while(|&(_, timestamp)| timestamp < threshold);
+        assert_eq!(
+            evicted, 2,
+            "Expected both items older than 200 to be evicted!"
+        );
+        assert_eq!(
+            buf.get_front(),
+            Some((3, 200)),
+            "The oldest remaining item should be the first one not older than 200!"
+        );
+
+        let evicted = buf.evict_front_while(|_| true);
+        assert_eq!(evicted, 3, "Expected every remaining item to be evicted!");
+        assert!(
+            buf.is_empty(),
+            "The RingBuffer should be empty after evicting every item!"
+        );
+        assert_eq!(
+            buf.evict_front_while(|_| true),
+            0,
+            "Nothing should be evicted from an empty RingBuffer!"
+        );
+    }
+
+    #[test]
+    fn test_send_sync() {
+        fn assert_send<T: Send>() {}
+        fn assert_sync<T: Sync>() {}
+
+        assert_send::<RingBuffer<i32, 8>>();
+        assert_sync::<RingBuffer<i32, 8>>();
+        assert_send::<RingBuffer<String, 8>>();
+        assert_sync::<RingBuffer<String, 8>>();
+
+        fn assert_unwind_safe<T: UnwindSafe + RefUnwindSafe>() {}
+
+        assert_unwind_safe::<RingBuffer<i32, 8>>();
+        assert_unwind_safe::<RingBuffer<String, 8>>();
+    }
+
+    #[test]
+    fn test_replace() {
+        let mut buf = wrapped_buffer();
+
+        assert_eq!(
+            buf.replace(2, 30),
+            Some(3),
+            "Replacing the middle item should return the old value!"
+        );
+        assert_eq!(
+            buf.replace(5, 60),
+            None,
+            "Replacing out of range should return None!"
+        );
+        assert_eq!(buf.len(), 5, "Replacing should never change the length!");
+
+        let mut contents = Vec::new();
+        while let Some(item) = buf.pop_front() {
+            contents.push(item);
+        }
+        assert_eq!(
+            contents,
+            vec![1, 2, 30, 4, 5],
+            "Only the middle item should have been replaced!"
+        );
+    }
+
+    #[test]
+    fn test_iter_matches_get() {
+        let buf = wrapped_buffer();
+
+        assert_eq!(
+            buf.iter().len(),
+            buf.len(),
+            "The iterator should yield exactly len() items!"
+        );
+        for (i, item) in buf.iter().enumerate() {
+            assert_eq!(
+                Some(item),
+                buf.get(i),
+                "The iterator position should match the logical index of get()!"
+            );
+        }
+        for n in 0..=buf.len() {
+            let mut iter = buf.iter();
+            assert_eq!(
+                iter.nth(n),
+                buf.get(n),
+                "nth() should match the logical index of get()!"
+            );
+            assert_eq!(
+                iter.next(),
+                buf.get(n + 1),
+                "nth() should leave the iterator after the skipped item!"
+            );
+        }
+        assert_eq!(
+            buf.get(buf.len()),
+            None,
+            "get() should return None past the last item!"
+        );
+        assert_eq!(
+            buf.iter().copied().collect::<Vec<_>>(),
+            vec![1, 2, 3, 4, 5],
+            "The iterator should yield the items from front to back!"
+        );
+    }
+
+    #[test]
+    fn test_pop_front_into() {
+        let mut buf = wrapped_buffer();
+        let mut expected = wrapped_buffer();
+
+        let mut out = [0; 3];
+        assert_eq!(
+            buf.pop_front_into(&mut out),
+            3,
+            "Expected to fill the whole output slice!"
+        );
+        for item in out {
+            assert_eq!(
+                Some(item),
+                expected.pop_front(),
+                "pop_front_into should match repeated pop_front calls!"
+            );
+        }
+
+        let mut out = [0; 4];
+        assert_eq!(
+            buf.pop_front_into(&mut out),
+            2,
+            "Expected to write only the remaining items!"
+        );
+        assert_eq!(
+            out[..2],
+            [expected.pop_front().unwrap(), expected.pop_front().unwrap()],
+            "pop_front_into should match repeated pop_front calls!"
+        );
+        assert!(
+            buf.is_empty(),
+            "The RingBuffer should be empty after popping every item!"
+        );
+    }
+
+    #[test]
+    fn test_fill_from_reader() {
+        let mut buf = RingBuffer::<u8, 8>::new();
+        buf.push(b'a');
+        buf.push(b'b');
+
+        let mut source: &[u8] = b"cdefghij";
+        assert_eq!(
+            buf.fill_from_reader(&mut source).unwrap(),
+            5,
+            "Expected to read only as many bytes as there is free space!"
+        );
+        assert!(
+            buf.is_full(),
+            "The RingBuffer should be full after filling!"
+        );
+        assert_eq!(
+            buf.iter().copied().collect::<Vec<_>>(),
+            b"abcdefg".to_vec(),
+            "The read bytes should follow the existing ones in order!"
+        );
+        assert_eq!(
+            source, b"hij",
+            "The unread bytes should remain in the source!"
+        );
+
+        let mut empty: &[u8] = &[];
+        buf.pop_front();
+        assert_eq!(
+            buf.fill_from_reader(&mut empty).unwrap(),
+            0,
+            "Nothing should be stored from an exhausted reader!"
+        );
+
+        let mut large = RingBuffer::<u8, 32>::new();
+        let bytes = (0..40).collect::<Vec<u8>>();
+        let mut source = bytes.as_slice();
+        assert_eq!(
+            large.fill_from_reader(&mut source).unwrap(),
+            31,
+            "Expected to fill the whole RingBuffer across several reads!"
+        );
+        assert_eq!(
+            large.iter().copied().collect::<Vec<_>>(),
+            bytes[..31].to_vec(),
+            "The bytes should be stored in order across reads!"
+        );
+    }
+
+    #[test]
+    fn test_drain_to_writer() {
+        let mut buf = RingBuffer::<u8, 6>::new();
+        for &byte in b"xyz" {
+            buf.push(byte);
+        }
+        for _ in 0..3 {
+            buf.pop_front();
+        }
+        for &byte in b"hello" {
+            buf.push(byte);
+        }
+
+        let mut out = Vec::new();
+        assert_eq!(
+            buf.drain_to_writer(&mut out).unwrap(),
+            5,
+            "Expected every buffered byte to be written!"
+        );
+        assert_eq!(out, b"hello", "The bytes should be written in order!");
+        assert!(
+            buf.is_empty(),
+            "The RingBuffer should be empty after draining!"
+        );
+        assert_eq!(
+            buf.drain_to_writer(&mut out).unwrap(),
+            0,
+            "Nothing should be written from an empty RingBuffer!"
+        );
+    }
+
+    #[test]
+    fn test_is_contiguous() {
+        let mut buf = RingBuffer::<usize, 4>::new();
+        assert!(
+            buf.is_contiguous(),
+            "An empty RingBuffer should be contiguous!"
+        );
+
+        buf.push(1);
+        buf.push(2);
+        assert!(
+            buf.is_contiguous(),
+            "A RingBuffer that has not wrapped should be contiguous!"
+        );
+
+        buf.pop_front();
+        buf.push(3);
+        buf.push(4);
+        assert!(
+            !buf.is_contiguous(),
+            "A RingBuffer whose items wrap around should not be contiguous!"
+        );
+
+        assert!(
+            !wrapped_buffer().is_contiguous(),
+            "A RingBuffer whose items wrap around should not be contiguous!"
+        );
+    }
+
+    #[test]
+    fn test_iterators_are_fused() {
+        let mut buf = wrapped_buffer();
+
+        let mut iter = buf.iter();
+        assert_eq!(iter.by_ref().count(), 5, "iter() should yield every item!");
+        assert_eq!(iter.next(), None, "iter() should stay exhausted!");
+        assert_eq!(iter.next(), None, "iter() should stay exhausted!");
+
+        let mut iter_mut = buf.iter_mut();
+        for item in iter_mut.by_ref() {
+            *item *= 10;
+        }
+        assert_eq!(iter_mut.next(), None, "iter_mut() should stay exhausted!");
+        assert_eq!(iter_mut.next(), None, "iter_mut() should stay exhausted!");
+        assert_eq!(
+            buf.iter().copied().collect::<Vec<_>>(),
+            vec![10, 20, 30, 40, 50],
+            "iter_mut() should have mutated every item in order!"
+        );
+
+        let mut drain = buf.drain();
+        assert_eq!(drain.next(), Some(10), "drain() should start at the front!");
+        drop(drain);
+        assert!(
+            buf.is_empty(),
+            "Dropping drain() early should still remove every item!"
+        );
+
+        let mut into_iter = wrapped_buffer().into_iter();
+        assert_eq!(
+            into_iter.by_ref().collect::<Vec<_>>(),
+            vec![1, 2, 3, 4, 5],
+            "into_iter() should yield every item from front to back!"
+        );
+        assert_eq!(into_iter.next(), None, "into_iter() should stay exhausted!");
+        assert_eq!(into_iter.next(), None, "into_iter() should stay exhausted!");
+    }
+
+    #[test]
+    fn test_try_for_each() {
+        let buf = wrapped_buffer();
+
+        let mut visited = Vec::new();
+        let result = buf.try_for_each(|&item| {
+            visited.push(item);
+            if item == 3 {
+                Err(item)
+            } else {
+                Ok(())
+            }
+        });
+        assert_eq!(result, Err(3), "Expected the error from the third item!");
+        assert_eq!(
+            visited,
+            vec![1, 2, 3],
+            "Iteration should stop at the first error!"
+        );
+
+        assert_eq!(
+            buf.try_for_each(|_| Ok::<(), ()>(())),
+            Ok(()),
+            "Expected Ok when no item errors!"
+        );
+    }
+
+    #[test]
+    fn test_usable_capacity() {
+        const USABLE: usize = 8;
+        let mut buf = RingBuffer::<i32, { USABLE + 1 }>::new();
+
+        assert_eq!(
+            buf.capacity(),
+            USABLE,
+            "A CAPACITY of N + 1 should hold N items!"
+        );
+        for i in 0..USABLE as i32 {
+            assert!(buf.push(i), "Expected to be able to push {USABLE} items!");
+        }
+        assert!(
+            !buf.push(USABLE as i32),
+            "Expected the RingBuffer to be full after {USABLE} items!"
+        );
+    }
+
+    #[test]
+    fn test_no_stale_items_after_popping() {
+        let mut buf = wrapped_buffer();
+
+        while buf.pop().is_some() {}
+        assert_eq!(
+            buf.get_back(),
+            None,
+            "There should be None in the back after popping every item!"
+        );
+        assert_eq!(
+            buf.get_front(),
+            None,
+            "There should be None in the front after popping every item!"
+        );
+
+        let mut buf = wrapped_buffer();
+        while buf.pop_front().is_some() {}
+        assert_eq!(
+            buf.get_back(),
+            None,
+            "There should be None in the back after popping every item from the front!"
+        );
+        assert_eq!(
+            buf.get_front(),
+            None,
+            "There should be None in the front after popping every item from the front!"
+        );
+        assert!(
+            buf.data.iter().all(Option::is_none),
+            "Every vacated slot should have been cleared!"
+        );
+    }
+
+    #[test]
+    fn test_partition() {
+        let buf = wrapped_buffer();
+
+        let (evens, odds) = buf.partition(|item| item % 2 == 0);
+        assert_eq!(
+            evens.iter().copied().collect::<Vec<_>>(),
+            vec![2, 4],
+            "The first buffer should hold the even items in order!"
+        );
+        assert_eq!(
+            odds.iter().copied().collect::<Vec<_>>(),
+            vec![1, 3, 5],
+            "The second buffer should hold the odd items in order!"
+        );
+        assert_eq!(buf.len(), 5, "The original buffer should be unchanged!");
+    }
+
+    #[test]
+    fn test_rposition() {
+        let mut buf = wrapped_buffer();
+        buf.replace(3, 2);
+
+        assert_eq!(
+            buf.position(|&item| item == 2),
+            Some(1),
+            "position() should return the earlier match!"
+        );
+        assert_eq!(
+            buf.rposition(|&item| item == 2),
+            Some(3),
+            "rposition() should return the later match!"
+        );
+        assert_eq!(
+            buf.rposition(|&item| item == 42),
+            None,
+            "rposition() should return None without a match!"
+        );
+    }
+
+    #[test]
+    fn test_ends() {
+        let mut buf = RingBuffer::<usize, 4>::new();
+        assert_eq!(buf.ends(), None, "An empty RingBuffer should have no ends!");
+
+        buf.push(1);
+        assert_eq!(
+            buf.ends(),
+            Some((&1, &1)),
+            "A single item should be both the front and the back!"
+        );
+
+        assert_eq!(
+            wrapped_buffer().ends(),
+            Some((&1, &5)),
+            "Expected the front and the back of the RingBuffer!"
+        );
+    }
+
+    #[test]
+    fn test_insert_sorted() {
+        let mut buf = RingBuffer::<i32, 6>::new();
+        for _ in 0..4 {
+            buf.push(0);
+            buf.pop_front();
+        }
+
+        for (value, index) in [(5, 0), (1, 0), (3, 1), (4, 2), (3, 2)] {
+            assert_eq!(
+                buf.insert_sorted(value),
+                Ok(index),
+                "Expected {value} to be inserted at index {index}!"
+            );
+        }
+        assert_eq!(
+            buf.iter().copied().collect::<Vec<_>>(),
+            vec![1, 3, 3, 4, 5],
+            "The RingBuffer should stay sorted!"
+        );
+        assert!(
+            !buf.is_contiguous(),
+            "The inserted items should wrap around the array!"
+        );
+        assert_eq!(
+            buf.insert_sorted(2),
+            Err(CapacityError(2)),
+            "Inserting into a full RingBuffer should hand the value back!"
+        );
+    }
+
+    #[test]
+    fn test_drain_front() {
+        for n in 0..=7 {
+            let mut buf = wrapped_buffer();
+            let mut expected = wrapped_buffer();
+            for _ in 0..n {
+                expected.pop_front();
+            }
+
+            assert_eq!(
+                buf.drain_front(n),
+                n.min(5),
+                "Expected to drain at most len() items!"
+            );
+            assert_eq!(
+                buf.len(),
+                expected.len(),
+                "drain_front should leave the same length as repeated pop_front calls!"
+            );
+            assert_eq!(
+                buf.iter().collect::<Vec<_>>(),
+                expected.iter().collect::<Vec<_>>(),
+                "drain_front should leave the same items as repeated pop_front calls!"
+            );
+            assert_eq!(
+                buf.get_front(),
+                expected.get_front(),
+                "The front should never be read from a drained slot!"
+            );
+            assert_eq!(
+                buf.get_back(),
+                expected.get_back(),
+                "The back should never be read from a drained slot!"
+            );
+        }
+
+        let mut buf = wrapped_buffer();
+        buf.drain_front(5);
+        buf.push_front(9);
+        assert_eq!(
+            buf.iter().copied().collect::<Vec<_>>(),
+            vec![9],
+            "Drained slots should not reappear after pushing again!"
+        );
+    }
+
+    #[test]
+    fn test_contains_subsequence() {
+        let buf = wrapped_buffer();
+
+        assert_eq!(
+            buf.contains_subsequence(&[2, 3, 4]),
+            Some(1),
+            "Expected to find a needle straddling the wrap boundary!"
+        );
+        assert_eq!(
+            buf.contains_subsequence(&[4, 5]),
+            Some(3),
+            "Expected to find a needle at the back!"
+        );
+        assert_eq!(
+            buf.contains_subsequence(&[2, 4]),
+            None,
+            "Items that are not consecutive should not match!"
+        );
+        assert_eq!(
+            buf.contains_subsequence(&[1, 2, 3, 4, 5, 6]),
+            None,
+            "A needle longer than the RingBuffer should not match!"
+        );
+        assert_eq!(
+            buf.contains_subsequence(&[]),
+            Some(0),
+            "An empty needle should match at the front!"
+        );
+    }
+
+    #[test]
+    fn test_split_at() {
+        let buf = wrapped_buffer();
+
+        let (left, right) = buf.split_at(3);
+        assert_eq!(
+            left.copied().collect::<Vec<_>>(),
+            vec![1, 2, 3],
+            "The left half should hold the items before the index!"
+        );
+        assert_eq!(
+            right.copied().collect::<Vec<_>>(),
+            vec![4, 5],
+            "The right half should hold the items from the index on!"
+        );
+
+        let (left, right) = buf.split_at(5);
+        assert_eq!(
+            left.count(),
+            5,
+            "Splitting at len() should keep everything left!"
+        );
+        assert_eq!(
+            right.count(),
+            0,
+            "Splitting at len() should leave nothing right!"
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_split_at_out_of_bounds() {
+        let _ = wrapped_buffer().split_at(6);
+    }
+
+    #[test]
+    fn test_mean() {
+        let mut buf = RingBuffer::<u32, 6>::new();
+        assert_eq!(buf.mean(), None, "An empty RingBuffer should have no mean!");
+
+        for i in [2, 4, 4, 4, 6] {
+            buf.push(i);
+        }
+        assert_eq!(buf.mean(), Some(4.0), "Expected the mean of 2, 4, 4, 4, 6!");
+    }
+
+    #[test]
+    fn test_try_from_iter() {
+        let buf = RingBuffer::<usize, 6>::try_from_iter(1..=5);
+        assert_eq!(
+            buf.map(|buf| buf.iter().copied().collect::<Vec<_>>()).ok(),
+            Some(vec![1, 2, 3, 4, 5]),
+            "Items that fit should be collected without error!"
+        );
+
+        let partial = match RingBuffer::<usize, 6>::try_from_iter(1..=8) {
+            Ok(_) => panic!("Expected an error for too many items!"),
+            Err(partial) => partial,
+        };
+        assert_eq!(
+            partial.iter().copied().collect::<Vec<_>>(),
+            vec![1, 2, 3, 4, 5],
+            "The error should carry the first capacity() items!"
+        );
+
+        let truncated: RingBuffer<usize, 6> = (1..=8).collect();
+        assert_eq!(
+            truncated.len(),
+            5,
+            "collect() should silently drop the items that do not fit!"
+        );
+    }
+
+    #[test]
+    fn test_shift_in() {
+        let mut buf = RingBuffer::<usize, 4>::new();
+
+        let shifted_out = (1..=7).map(|i| buf.shift_in(i)).collect::<Vec<_>>();
+        assert_eq!(
+            shifted_out,
+            vec![None, None, None, Some(1), Some(2), Some(3), Some(4)],
+            "Once full, the shifted-out items should be the input delayed by the capacity!"
+        );
+        assert_eq!(
+            buf.iter().copied().collect::<Vec<_>>(),
+            vec![5, 6, 7],
+            "The RingBuffer should hold the newest items!"
+        );
+    }
+
+    #[test]
+    fn test_counts() {
+        let buf: RingBuffer<&str, 8> = ["a", "b", "a", "c", "a", "b"].into_iter().collect();
+
+        let counts = buf.counts();
+        assert_eq!(counts.len(), 3, "Expected three distinct items!");
+        assert_eq!(counts["a"], 3, "Expected \"a\" three times!");
+        assert_eq!(counts["b"], 2, "Expected \"b\" twice!");
+        assert_eq!(counts["c"], 1, "Expected \"c\" once!");
+        assert!(
+            RingBuffer::<&str, 8>::new().counts().is_empty(),
+            "An empty RingBuffer should have no counts!"
+        );
+    }
+
+    #[test]
+    fn test_clone_from() {
+        let sources = [
+            ["a", "b", "c"].map(String::from).to_vec(),
+            Vec::new(),
+            ["d", "e", "f", "g", "h"].map(String::from).to_vec(),
+            ["i"].map(String::from).to_vec(),
+        ];
+
+        let mut destination = RingBuffer::<String, 6>::new();
+        for contents in sources {
+            let mut source = RingBuffer::<String, 6>::new();
+            source.push("x".to_string());
+            source.pop_front();
+            for item in contents.iter().cloned() {
+                source.push(item);
+            }
+
+            destination.clone_from(&source);
+            assert_eq!(
+                destination.iter().collect::<Vec<_>>(),
+                contents.iter().collect::<Vec<_>>(),
+                "The destination should hold the same items as the source!"
+            );
+            assert_eq!(
+                destination.len(),
+                source.len(),
+                "The destination should have the same length as the source!"
+            );
+        }
+
+        let source = wrapped_buffer();
+        assert_eq!(
+            source.clone().iter().collect::<Vec<_>>(),
+            source.iter().collect::<Vec<_>>(),
+            "clone() should hold the same items as the original!"
+        );
+    }
+
+    #[test]
+    fn test_clone_from_panicking_clone() {
+        #[derive(Debug, Default, PartialEq)]
+        struct PanicOnClone(i32);
+
+        impl Clone for PanicOnClone {
+            fn clone(&self) -> Self {
+                assert!(self.0 != 3, "clone panics");
+                Self(self.0)
+            }
+        }
+
+        let mut source = RingBuffer::<PanicOnClone, 6>::new();
+        for i in 1..=5 {
+            source.push(PanicOnClone(i));
+        }
+        let mut destination = RingBuffer::<PanicOnClone, 6>::new();
+        for i in 10..13 {
+            destination.push(PanicOnClone(i));
+        }
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            destination.clone_from(&source);
+        }));
+        assert!(result.is_err(), "Expected the element clone to panic!");
+        assert_eq!(
+            destination.len(),
+            destination.iter().count(),
+            "len() should match the items yielded after a panic!"
+        );
+        assert!(
+            destination.is_empty(),
+            "The destination should be left empty after a panic!"
+        );
+        destination.push(PanicOnClone(7));
+        assert_eq!(
+            destination.get_front_ref(),
+            &Some(PanicOnClone(7)),
+            "The destination should still be usable after a panic!"
+        );
+    }
+
+    #[test]
+    fn test_front_n_mut() {
+        let mut buf = wrapped_buffer();
+
+        for item in buf.front_n_mut(3) {
+            *item *= 10;
+        }
+        assert_eq!(
+            buf.iter().copied().collect::<Vec<_>>(),
+            vec![10, 20, 30, 4, 5],
+            "Only the first three items should have been mutated!"
+        );
+        assert_eq!(
+            buf.front_n_mut(10).count(),
+            5,
+            "Asking for more than len() items should yield every item!"
+        );
+    }
+
+    #[test]
+    fn test_can_fit() {
+        let mut buf = RingBuffer::<usize, 6>::new();
+        buf.push(1);
+        buf.push(2);
+
+        for additional in 0..=6 {
+            assert_eq!(
+                buf.can_fit(additional),
+                additional <= buf.remaining_capacity(),
+                "can_fit({additional}) should be false exactly when it exceeds remaining_capacity()!"
+            );
+        }
+        assert!(buf.can_fit(3), "Three more items should fit!");
+        assert!(!buf.can_fit(4), "Four more items should not fit!");
+    }
+
+    #[test]
+    fn test_drain_range() {
+        let mut buf = wrapped_buffer();
+
+        assert_eq!(
+            buf.drain_range(1..4).collect::<Vec<_>>(),
+            vec![2, 3, 4],
+            "Expected the drained items in order!"
+        );
+        assert_eq!(buf.len(), 2, "Expected two items to remain!");
+        assert_eq!(
+            buf.iter().copied().collect::<Vec<_>>(),
+            vec![1, 5],
+            "The remaining items should close the gap in order!"
+        );
+        assert_eq!(buf.get_back(), Some(5), "The back should be Some(5)!");
+
+        assert_eq!(
+            buf.drain_range(1..1).count(),
+            0,
+            "An empty range should drain nothing!"
+        );
+        buf.push(6);
+        assert_eq!(
+            buf.iter().copied().collect::<Vec<_>>(),
+            vec![1, 5, 6],
+            "Pushing after draining should append at the new back!"
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_drain_range_out_of_bounds() {
+        let _ = wrapped_buffer().drain_range(3..6);
+    }
+
+    #[test]
+    fn test_front_or() {
+        let mut buf = RingBuffer::<usize, 4>::new();
+        assert_eq!(
+            buf.front_or(42),
+            42,
+            "An empty RingBuffer should return the default!"
+        );
+
+        buf.push(1);
+        buf.push(2);
+        assert_eq!(
+            buf.front_or(42),
+            1,
+            "Expected the front item instead of the default!"
+        );
+    }
+
+    #[test]
+    fn test_eq_ignore_order() {
+        let buf = wrapped_buffer();
+
+        let shuffled: RingBuffer<usize, 6> = [4, 1, 5, 3, 2].into_iter().collect();
+        assert!(
+            buf.eq_ignore_order(&shuffled),
+            "The same items in a different order should compare equal!"
+        );
+
+        let different: RingBuffer<usize, 6> = [4, 1, 5, 3, 3].into_iter().collect();
+        assert!(
+            !buf.eq_ignore_order(&different),
+            "Buffers with a differing item should not compare equal!"
+        );
+        assert!(
+            !different.eq_ignore_order(&buf),
+            "The comparison should be symmetric!"
+        );
+    }
+
+    #[test]
+    fn test_concat() {
+        let a: RingBuffer<usize, 4> = [1, 2, 3].into_iter().collect();
+
+        let joined = RingBuffer::<usize, 9>::concat(a.clone(), wrapped_buffer());
+        assert_eq!(
+            joined.map(|buf| buf.iter().copied().collect::<Vec<_>>()),
+            Some(vec![1, 2, 3, 1, 2, 3, 4, 5]),
+            "Expected the items of b after the items of a!"
+        );
+
+        assert!(
+            RingBuffer::<usize, 8>::concat(a, wrapped_buffer()).is_none(),
+            "Concatenating into a too small RingBuffer should fail!"
+        );
+    }
+
+    #[test]
+    fn test_find_map() {
+        let buf = wrapped_buffer();
+
+        assert_eq!(
+            buf.find_map(|&item| (item == 3).then(|| item * 100)),
+            Some(300),
+            "Expected the transformed value of the only match!"
+        );
+        assert_eq!(
+            buf.find_map(|&item| (item > 5).then_some(item)),
+            None,
+            "Expected None without a match!"
+        );
+    }
+
+    #[test]
+    fn test_fill_ratio() {
+        let mut buf = RingBuffer::<usize, 5>::new();
+        assert_eq!(
+            buf.fill_ratio(),
+            0.0,
+            "An empty RingBuffer should be 0% full!"
+        );
+
+        buf.push(1);
+        buf.push(2);
+        assert_eq!(
+            buf.fill_ratio(),
+            0.5,
+            "Expected the RingBuffer to be half full!"
+        );
+
+        buf.push(3);
+        buf.push(4);
+        assert_eq!(
+            buf.fill_ratio(),
+            1.0,
+            "A full RingBuffer should be 100% full!"
+        );
+
+        assert_eq!(
+            RingBuffer::<usize, 1>::new().fill_ratio(),
+            1.0,
+            "A RingBuffer without usable capacity should be 100% full!"
+        );
+    }
+
+    #[test]
+    fn test_slice() {
+        let buf = wrapped_buffer();
+
+        assert_eq!(
+            buf.slice(1..4),
+            vec![&2, &3, &4],
+            "Expected the items across the wrap boundary!"
+        );
+        assert!(
+            buf.slice(2..2).is_empty(),
+            "An empty range should be empty!"
+        );
+        assert_eq!(
+            buf.slice(0..5).len(),
+            5,
+            "The full range should hold every item!"
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_slice_out_of_bounds() {
+        let _ = wrapped_buffer().slice(4..6);
+    }
+
+    #[test]
+    fn test_compact_to() {
+        for (keep, kept, dropped) in [
+            (Keep::Newest, ["d", "e"], ["a", "b", "c"]),
+            (Keep::Oldest, ["a", "b"], ["c", "d", "e"]),
+        ] {
+            let mut buf = RingBuffer::<Rc<String>, 6>::new();
+            for _ in 0..4 {
+                buf.push(Rc::default());
+                buf.pop_front();
+            }
+            let mut handles = Vec::new();
+            for name in ["a", "b", "c", "d", "e"] {
+                let item = Rc::new(name.to_string());
+                handles.push((name, Rc::downgrade(&item)));
+                buf.push(item);
+            }
+
+            buf.compact_to(2, keep);
+            assert_eq!(
+                buf.iter().map(|item| item.as_str()).collect::<Vec<_>>(),
+                kept,
+                "Expected {keep:?} to keep {kept:?}!"
+            );
+            for (name, handle) in &handles {
+                assert_eq!(
+                    Weak::upgrade(handle).is_none(),
+                    dropped.contains(name),
+                    "Expected exactly the dropped items to be destroyed!"
+                );
+            }
+
+            buf.compact_to(5, keep);
+            assert_eq!(
+                buf.len(),
+                2,
+                "Compacting to a larger len() should do nothing!"
+            );
+        }
+    }
+
+    #[test]
+    fn test_stride() {
+        let buf = wrapped_buffer();
+
+        assert_eq!(
+            buf.stride(2).copied().collect::<Vec<_>>(),
+            vec![1, 3, 5],
+            "Expected every second item starting at the front!"
+        );
+        assert_eq!(
+            buf.stride(1).count(),
+            5,
+            "A step of 1 should yield every item!"
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_stride_zero() {
+        let _ = wrapped_buffer().stride(0);
+    }
+
+    #[test]
+    fn test_push_overwrite_with() {
+        let mut buf = RingBuffer::<usize, 4>::new();
+
+        let mut evicted = Vec::new();
+        for i in 1..=6 {
+            buf.push_overwrite_with(i, |item| evicted.push(item));
+        }
+        assert_eq!(
+            evicted,
+            vec![1, 2, 3],
+            "The callback should receive every evicted item in order!"
+        );
+        assert_eq!(
+            buf.iter().copied().collect::<Vec<_>>(),
+            vec![4, 5, 6],
+            "The RingBuffer should hold the newest items!"
+        );
+    }
+
+    #[test]
+    fn test_zip_with() {
+        let a = wrapped_buffer();
+        let b: RingBuffer<usize, 6> = [10, 20, 30, 40, 50].into_iter().collect();
+
+        let sums = a.zip_with(&b, |x, y| x + y);
+        assert_eq!(
+            sums.map(|buf| buf.iter().copied().collect::<Vec<_>>()),
+            Some(vec![11, 22, 33, 44, 55]),
+            "Expected the pairwise sums in order!"
+        );
+
+        let shorter: RingBuffer<usize, 6> = [1, 2].into_iter().collect();
+        assert!(
+            a.zip_with(&shorter, |x, y| x + y).is_none(),
+            "Zipping buffers of different lengths should fail!"
+        );
+    }
+
+    #[test]
+    fn test_into_array() {
+        assert_eq!(
+            wrapped_buffer().into_array::<5>().ok(),
+            Some([1, 2, 3, 4, 5]),
+            "A full RingBuffer should convert into an array in order!"
+        );
+
+        let mut buf = wrapped_buffer();
+        buf.pop();
+        let buf = match buf.into_array::<5>() {
+            Ok(_) => panic!("Expected a length mismatch to fail!"),
+            Err(buf) => buf,
+        };
+        assert_eq!(
+            buf.iter().copied().collect::<Vec<_>>(),
+            vec![1, 2, 3, 4],
+            "A failed conversion should hand the RingBuffer back unchanged!"
+        );
+    }
+
+    #[test]
+    fn test_push_iter() {
+        let mut buf = RingBuffer::<usize, 6>::new();
+        buf.push(0);
+
+        let remaining = buf.remaining_capacity();
+        let mut source = 1..=10;
+        assert_eq!(
+            buf.push_iter(&mut source),
+            remaining,
+            "Expected to push exactly remaining_capacity() items!"
+        );
+        assert!(buf.is_full(), "The RingBuffer should be full!");
+        assert_eq!(
+            source.next(),
+            Some(5),
+            "Items that do not fit should be left in the iterator!"
+        );
+        assert_eq!(
+            buf.push_iter(11..),
+            0,
+            "Nothing should be pushed into a full RingBuffer!"
+        );
+    }
+
+    #[test]
+    fn test_iter_cycle() {
+        let buf = wrapped_buffer();
+
+        assert_eq!(
+            buf.iter_cycle()
+                .take(2 * buf.len() + 1)
+                .copied()
+                .collect::<Vec<_>>(),
+            vec![1, 2, 3, 4, 5, 1, 2, 3, 4, 5, 1],
+            "Expected the items to repeat from the front after the back!"
+        );
+        assert_eq!(
+            RingBuffer::<usize, 4>::new().iter_cycle().next(),
+            None,
+            "Cycling an empty RingBuffer should yield nothing!"
+        );
+    }
+
+    #[test]
+    fn test_move_to_back_and_front() {
+        let mut buf = wrapped_buffer();
+
+        buf.move_to_back(2);
+        assert_eq!(
+            buf.iter().copied().collect::<Vec<_>>(),
+            vec![1, 2, 4, 5, 3],
+            "The middle item should have moved to the back!"
+        );
+
+        buf.move_to_front(3);
+        assert_eq!(
+            buf.iter().copied().collect::<Vec<_>>(),
+            vec![5, 1, 2, 4, 3],
+            "The item at index 3 should have moved to the front!"
+        );
+
+        buf.move_to_front(0);
+        buf.move_to_back(4);
+        assert_eq!(
+            buf.iter().copied().collect::<Vec<_>>(),
+            vec![5, 1, 2, 4, 3],
+            "Moving an end item to its own end should change nothing!"
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_move_to_back_out_of_bounds() {
+        wrapped_buffer().move_to_back(5);
+    }
+
+    #[test]
+    fn test_split_first_and_last() {
+        let empty = RingBuffer::<usize, 4>::new();
+        assert!(
+            empty.split_first().is_none(),
+            "An empty RingBuffer should have no first item!"
+        );
+        assert!(
+            empty.split_last().is_none(),
+            "An empty RingBuffer should have no last item!"
+        );
+
+        let buf = wrapped_buffer();
+        let (first, rest) = buf.split_first().unwrap();
+        assert_eq!(*first, 1, "Expected the front item first!");
+        assert_eq!(
+            rest.copied().collect::<Vec<_>>(),
+            vec![2, 3, 4, 5],
+            "Expected the remaining items after the front!"
+        );
+
+        let (last, rest) = buf.split_last().unwrap();
+        assert_eq!(*last, 5, "Expected the back item last!");
+        assert_eq!(
+            rest.copied().collect::<Vec<_>>(),
+            vec![1, 2, 3, 4],
+            "Expected the remaining items before the back!"
+        );
+    }
+
+    #[test]
+    fn test_resize_with() {
+        let mut buf = RingBuffer::<usize, 6>::new();
+        buf.push(1);
+
+        let mut next = 1;
+        buf.resize_with(3, || {
+            next += 1;
+            next
+        });
+        assert_eq!(
+            buf.iter().copied().collect::<Vec<_>>(),
+            vec![1, 2, 3],
+            "Growing should append values from the closure!"
+        );
+
+        buf.resize_with(10, || 0);
+        assert_eq!(buf.len(), 5, "Growing should stop at capacity()!");
+        assert_eq!(
+            buf.iter().copied().collect::<Vec<_>>(),
+            vec![1, 2, 3, 0, 0],
+            "Growing should append values from the closure!"
+        );
+
+        buf.resize_with(2, || 0);
+        assert_eq!(
+            buf.iter().copied().collect::<Vec<_>>(),
+            vec![1, 2],
+            "Shrinking should drop items from the back!"
+        );
+    }
+
+    #[test]
+    fn test_rchunks() {
+        let mut buf = RingBuffer::<usize, 9>::new();
+        for _ in 0..5 {
+            buf.push(0);
+            buf.pop_front();
+        }
+        for i in 1..=7 {
+            buf.push(i);
+        }
+        assert!(
+            !buf.is_contiguous(),
+            "The items should wrap around the array!"
+        );
+
+        assert_eq!(
+            buf.rchunks(3).collect::<Vec<_>>(),
+            vec![vec![&5, &6, &7], vec![&2, &3, &4], vec![&1]],
+            "Expected chunks of 3 from the back, with the oldest one shorter!"
+        );
+        assert_eq!(
+            RingBuffer::<usize, 4>::new().rchunks(3).count(),
+            0,
+            "An empty RingBuffer should have no chunks!"
+        );
+    }
+
+    #[test]
+    fn test_iter_indexed() {
+        let buf = wrapped_buffer();
+
+        let indexed = buf.iter_indexed().collect::<Vec<_>>();
+        assert_eq!(
+            indexed.iter().map(|&(i, _)| i).collect::<Vec<_>>(),
+            (0..buf.len()).collect::<Vec<_>>(),
+            "The indices should ascend from 0 to len() - 1!"
+        );
+        for (i, item) in indexed {
+            assert_eq!(
+                Some(item),
+                buf.get(i),
+                "Every index should match the item get() returns!"
+            );
+        }
+    }
+
+    #[test]
+    fn test_max_and_min_by_key() {
+        let mut buf = RingBuffer::<String, 6>::new();
+        for _ in 0..4 {
+            buf.push(String::new());
+            buf.pop_front();
+        }
+        for word in ["ring", "a", "buffer", "of", "words"] {
+            buf.push(word.to_string());
+        }
+
+        assert_eq!(
+            buf.max_by_key(String::len).map(String::as_str),
+            Some("buffer"),
+            "Expected the longest string!"
+        );
+        assert_eq!(
+            buf.min_by_key(String::len).map(String::as_str),
+            Some("a"),
+            "Expected the shortest string!"
+        );
+        assert_eq!(
+            RingBuffer::<String, 6>::new().max_by_key(String::len),
+            None,
+            "An empty RingBuffer should have no maximum!"
+        );
+    }
+
+    #[test]
+    fn test_prefix_sums() {
+        assert_eq!(
+            wrapped_buffer().prefix_sums(),
+            vec![1, 3, 6, 10, 15],
+            "Expected the running sums across the wrap boundary!"
+        );
+        assert!(
+            RingBuffer::<usize, 4>::new().prefix_sums().is_empty(),
+            "An empty RingBuffer should have no prefix sums!"
+        );
+    }
+
+    #[test]
+    fn test_contains_any() {
+        let buf = wrapped_buffer();
+
+        assert!(
+            buf.contains_any(&[9, 3, 7]),
+            "Expected a match for one of the candidates!"
+        );
+        assert!(
+            !buf.contains_any(&[0, 6, 7]),
+            "Expected no match for any of the candidates!"
+        );
+        assert!(!buf.contains_any(&[]), "No candidates should never match!");
+    }
+
+    #[test]
+    fn test_back_n_and_last_n() {
+        let buf = wrapped_buffer();
+
+        assert_eq!(
+            buf.back_n(3).copied().collect::<Vec<_>>(),
+            vec![5, 4, 3],
+            "back_n() should yield the newest items newest first!"
+        );
+        assert_eq!(
+            buf.last_n(3).copied().collect::<Vec<_>>(),
+            vec![3, 4, 5],
+            "last_n() should yield the newest items oldest first!"
+        );
+        assert_eq!(
+            buf.back_n(10).count(),
+            5,
+            "Asking for more than len() items should yield every item!"
+        );
+        assert_eq!(
+            buf.last_n(10).count(),
+            5,
+            "Asking for more than len() items should yield every item!"
+        );
+    }
+
+    #[test]
+    fn test_apply() {
+        let mut buf = wrapped_buffer();
+
+        buf.apply(|item| *item *= 2);
+        assert_eq!(
+            buf.iter().copied().collect::<Vec<_>>(),
+            vec![2, 4, 6, 8, 10],
+            "Every item should have been doubled!"
+        );
+    }
+
+    #[test]
+    fn test_nth_from_back() {
+        let buf = wrapped_buffer();
+
+        assert_eq!(buf.nth_from_back(0), Some(&5), "n = 0 should be the back!");
+        assert_eq!(
+            buf.nth_from_back(4),
+            Some(&1),
+            "The last valid n should be the front!"
+        );
+        assert_eq!(
+            buf.nth_from_back(5),
+            None,
+            "n = len() should be out of range!"
+        );
+        assert_eq!(
+            RingBuffer::<usize, 4>::new().nth_from_back(0),
+            None,
+            "An empty RingBuffer should have nothing at the back!"
+        );
+    }
+
+    #[test]
+    fn test_capacity_info() {
+        assert_eq!(
+            wrapped_buffer().capacity_info(),
+            CapacityInfo {
+                raw_capacity: 6,
+                usable_capacity: 5,
+                len: 5,
+                remaining: 0,
+                wasted_slots: 1,
+            },
+            "Expected the slot usage of a full RingBuffer!"
+        );
+
+        let mut buf = RingBuffer::<usize, 10>::new();
+        buf.push(1);
+        buf.push(2);
+        assert_eq!(
+            buf.capacity_info(),
+            CapacityInfo {
+                raw_capacity: 10,
+                usable_capacity: 9,
+                len: 2,
+                remaining: 7,
+                wasted_slots: 1,
+            },
+            "Expected the slot usage of a partially filled RingBuffer!"
+        );
+    }
+
+    #[test]
+    fn test_extend_from_refs() {
+        let mut buf = RingBuffer::<usize, 6>::new();
+        buf.push(0);
+
+        let source = [1, 2, 3];
+        buf.extend(source.iter());
+        assert_eq!(
+            buf.iter().copied().collect::<Vec<_>>(),
+            vec![0, 1, 2, 3],
+            "The referenced items should be copied in order!"
+        );
+
+        buf.extend(&[4, 5, 6]);
+        assert_eq!(
+            buf.iter().copied().collect::<Vec<_>>(),
+            vec![0, 1, 2, 3, 4],
+            "Extending should stop once the RingBuffer is full!"
+        );
+    }
+
+    #[test]
+    fn test_drain_while() {
+        let mut buf: RingBuffer<usize, 8> = [2, 4, 6, 7, 8, 10].into_iter().collect();
+
+        assert_eq!(
+            buf.drain_while(|item| item % 2 == 0).collect::<Vec<_>>(),
+            vec![2, 4, 6],
+            "Expected the leading run of even items!"
+        );
+        assert_eq!(
+            buf.get_front(),
+            Some(7),
+            "The first odd item should remain at the front!"
+        );
+        assert_eq!(buf.len(), 3, "Expected three items to remain!");
+    }
+
+    #[test]
+    fn test_drain_while_partly_consumed() {
+        let mut buf: RingBuffer<usize, 8> = [2, 4, 6, 7].into_iter().collect();
+
+        assert_eq!(
+            buf.drain_while(|item| item % 2 == 0).next(),
+            Some(2),
+            "Expected the first even item!"
+        );
+        assert_eq!(
+            buf.iter().copied().collect::<Vec<_>>(),
+            vec![4, 6, 7],
+            "Only the yielded item should have been removed!"
+        );
+    }
+
+    #[test]
+    fn test_normalize() {
+        let mut buf = wrapped_buffer();
+        buf.normalize();
+        assert_eq!(
+            (buf.front, buf.back),
+            (3, 2),
+            "A non-empty RingBuffer should be left alone!"
+        );
+
+        while buf.pop_front().is_some() {}
+        assert_ne!(buf.front, 0, "Expected the indices to be left mid-array!");
+        buf.normalize();
+        assert_eq!(
+            (buf.front, buf.back),
+            (0, 0),
+            "An empty RingBuffer should have both indices reset to 0!"
+        );
+
+        buf.push(1);
+        buf.push(2);
+        assert!(
+            buf.is_contiguous(),
+            "Pushing after normalizing should start at the array's beginning!"
+        );
+    }
+
+    #[test]
+    fn test_get_pair_mut() {
+        let mut buf = wrapped_buffer();
+
+        let (a, b) = buf.get_pair_mut(4, 0).unwrap();
+        std::mem::swap(a, b);
+        let (a, b) = buf.get_pair_mut(1, 2).unwrap();
+        *a += 10;
+        *b += 20;
+        assert_eq!(
+            buf.iter().copied().collect::<Vec<_>>(),
+            vec![5, 12, 23, 4, 1],
+            "Both items of each pair should have been mutated!"
+        );
+
+        assert!(
+            buf.get_pair_mut(2, 2).is_none(),
+            "The same index twice should be rejected!"
+        );
+        assert!(
+            buf.get_pair_mut(0, 5).is_none(),
+            "An out-of-range index should be rejected!"
+        );
+    }
+
+    #[test]
+    fn test_scan() {
+        let buf: RingBuffer<usize, 8> = [3, 1, 4, 1, 5, 9, 2].into_iter().collect();
+
+        let running_max = buf.scan(0, |max, &item| {
+            *max = item.max(*max);
+            *max
+        });
+        assert_eq!(
+            running_max.iter().copied().collect::<Vec<_>>(),
+            vec![3, 3, 4, 4, 5, 9, 9],
+            "Expected the running maximum at each position!"
+        );
+    }
+
+    #[test]
+    fn test_is_sorted() {
+        assert!(
+            wrapped_buffer().is_sorted(),
+            "A sorted wrapped RingBuffer should be sorted!"
+        );
+
+        let mut buf = wrapped_buffer();
+        buf.move_to_back(1);
+        assert!(
+            !buf.is_sorted(),
+            "An unsorted RingBuffer should not be sorted!"
+        );
+
+        let mut buf = RingBuffer::<usize, 4>::new();
+        assert!(buf.is_sorted(), "An empty RingBuffer should be sorted!");
+        buf.push(1);
+        assert!(buf.is_sorted(), "A single item should be sorted!");
+    }
+
+    #[test]
+    fn test_rotate_until() {
+        let mut buf = wrapped_buffer();
+
+        assert!(
+            buf.rotate_until(|&item| item == 4),
+            "Expected a matching item to rotate to!"
+        );
+        assert_eq!(
+            buf.iter().copied().collect::<Vec<_>>(),
+            vec![4, 5, 1, 2, 3],
+            "The matching item should be at the front with the order kept cyclically!"
+        );
+
+        assert!(
+            !buf.rotate_until(|&item| item == 42),
+            "Expected no matching item to rotate to!"
+        );
+        assert_eq!(
+            buf.iter().copied().collect::<Vec<_>>(),
+            vec![4, 5, 1, 2, 3],
+            "The RingBuffer should not rotate without a match!"
+        );
+    }
+
+    #[test]
+    fn test_clear_range() {
+        let mut buf = RingBuffer::<Rc<String>, 6>::new();
+        for _ in 0..4 {
+            buf.push(Rc::default());
+            buf.pop_front();
+        }
+        let mut handles = Vec::new();
+        for name in ["a", "b", "c", "d", "e"] {
+            let item = Rc::new(name.to_string());
+            handles.push(Rc::downgrade(&item));
+            buf.push(item);
+        }
+
+        buf.clear_range(1..3);
+        assert_eq!(
+            buf.iter().map(|item| item.as_str()).collect::<Vec<_>>(),
+            vec!["a", "d", "e"],
+            "The remaining items should keep their order!"
+        );
+        assert_eq!(
+            handles
+                .iter()
+                .map(|handle| handle.upgrade().is_some())
+                .collect::<Vec<_>>(),
+            vec![true, false, false, true, true],
+            "Exactly the cleared items should be destroyed!"
+        );
+    }
+
+    #[test]
+    fn test_ends_mut() {
+        let mut buf = wrapped_buffer();
+
+        let (front, back) = buf.ends_mut().unwrap();
+        *front += 100;
+        *back += 200;
+        assert_eq!(
+            buf.iter().copied().collect::<Vec<_>>(),
+            vec![101, 2, 3, 4, 205],
+            "Both ends should have been mutated!"
+        );
+
+        let mut buf = RingBuffer::<usize, 4>::new();
+        assert!(buf.ends_mut().is_none(), "An empty RingBuffer has no ends!");
+        buf.push(1);
+        assert!(
+            buf.ends_mut().is_none(),
+            "A single item cannot be borrowed mutably twice!"
+        );
+    }
+
+    #[test]
+    fn test_flatten() {
+        let mut buf = RingBuffer::<Vec<i32>, 4>::new();
+        buf.push(Vec::new());
+        buf.pop_front();
+        buf.push(vec![1, 2]);
+        buf.push(Vec::new());
+        buf.push(vec![3, 4, 5]);
+
+        assert_eq!(
+            buf.flatten(),
+            vec![1, 2, 3, 4, 5],
+            "Expected the inner items concatenated in order!"
+        );
+        assert_eq!(buf.len(), 3, "The original buffer should be unchanged!");
+    }
+
+    #[test]
+    fn test_copy_within() {
+        let mut buf = wrapped_buffer();
+        let mut expected = [1, 2, 3, 4, 5];
+
+        buf.copy_within(0..3, 2);
+        expected.copy_within(0..3, 2);
+        assert_eq!(
+            buf.iter().copied().collect::<Vec<_>>(),
+            expected,
+            "Copying forward with overlap should match slice semantics!"
+        );
+
+        buf.copy_within(2..5, 0);
+        expected.copy_within(2..5, 0);
+        assert_eq!(
+            buf.iter().copied().collect::<Vec<_>>(),
+            expected,
+            "Copying backward with overlap should match slice semantics!"
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_copy_within_out_of_bounds() {
+        wrapped_buffer().copy_within(0..3, 3);
+    }
+
+    #[test]
+    fn test_split_when() {
+        let buf: RingBuffer<usize, 10> = [1, 2, 0, 3, 0, 0, 4, 5].into_iter().collect();
+
+        let segments = buf
+            .split_when(|&item| item == 0)
+            .iter()
+            .map(|segment| segment.iter().copied().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            segments,
+            vec![vec![1, 2], vec![3], vec![], vec![4, 5]],
+            "Expected one segment per delimiter-separated run!"
+        );
+        assert_eq!(
+            wrapped_buffer().split_when(|&item| item == 0).len(),
+            1,
+            "Without delimiters there should be a single segment!"
+        );
+    }
+
+    #[test]
+    fn test_reserve() {
+        let mut buf = RingBuffer::<usize, 6>::new();
+        buf.push(1);
+        buf.push(2);
+
+        assert_eq!(
+            buf.reserve(3),
+            Ok(()),
+            "Reserving the remaining room should work!"
+        );
+        assert_eq!(
+            buf.reserve(4),
+            Err(CapacityError(())),
+            "Reserving more than the remaining room should fail!"
+        );
+    }
+
+    #[test]
+    fn test_copy_range() {
+        let buf = wrapped_buffer();
+
+        assert_eq!(
+            buf.copy_range::<3>(1),
+            Some([2, 3, 4]),
+            "Expected the window across the wrap boundary!"
+        );
+        assert_eq!(
+            buf.copy_range::<3>(3),
+            None,
+            "A window past len() should fail!"
+        );
+        assert_eq!(
+            buf.copy_range::<0>(5),
+            Some([]),
+            "An empty window at len() should work!"
+        );
+    }
+
+    #[test]
+    fn test_mean_matches_naive_scan() {
+        let mut buf = RingBuffer::<u32, 32>::new();
+        for _ in 0..20 {
+            buf.push(0);
+            buf.pop_front();
+        }
+        for i in 0..31 {
+            buf.push(i * 7 % 13);
+        }
+        assert!(
+            !buf.is_contiguous(),
+            "The items should wrap around the array!"
+        );
+
+        let naive_sum: f64 = (0..buf.len())
+            .filter_map(|i| buf.get(i))
+            .map(|&item| f64::from(item))
+            .sum();
+        assert_eq!(
+            buf.mean(),
+            Some(naive_sum / buf.len() as f64),
+            "mean() over the contiguous segments should match an indexed scan!"
+        );
+    }
+
+    #[test]
+    fn test_partition_point() {
+        let buf = wrapped_buffer();
+
+        assert_eq!(
+            buf.partition_point(|&item| item < 4),
+            3,
+            "Expected the index of the first item not below 4!"
+        );
+        assert_eq!(
+            buf.partition_point(|_| true),
+            buf.len(),
+            "Expected len() when every item satisfies the predicate!"
+        );
+        assert_eq!(
+            buf.partition_point(|_| false),
+            0,
+            "Expected 0 when no item satisfies the predicate!"
+        );
+    }
+
+    #[test]
+    fn test_on_overflow() {
+        let mut buf = RingBuffer::<usize, 4>::new();
+        let rejected = Arc::new(AtomicUsize::new(0));
+
+        let counter = Arc::clone(&rejected);
+        buf.on_overflow(Box::new(move |_| {
+            counter.fetch_add(1, Ordering::Relaxed);
+        }));
+
+        for i in 0..3 {
+            assert!(buf.push(i), "Expected to fill the RingBuffer!");
+        }
+        assert_eq!(
+            rejected.load(Ordering::Relaxed),
+            0,
+            "The callback should not fire while there is room!"
+        );
+
+        assert!(
+            !buf.push(3),
+            "push() should still fail on a full RingBuffer!"
+        );
+        assert!(
+            !buf.push_front(4),
+            "push_front() should still fail on a full RingBuffer!"
+        );
+        assert!(
+            !buf.push(5),
+            "push() should still fail on a full RingBuffer!"
+        );
+        assert_eq!(
+            rejected.load(Ordering::Relaxed),
+            3,
+            "The callback should fire once per rejected item!"
+        );
+        assert_eq!(
+            buf.iter().copied().collect::<Vec<_>>(),
+            vec![0, 1, 2],
+            "Rejected items should not be stored!"
+        );
+    }
+
+    #[test]
+    fn test_iter_mut_rev() {
+        let mut buf = wrapped_buffer();
+
+        let mut iter = buf.iter_mut();
+        let mut from_back = true;
+        while let Some(item) = if from_back {
+            iter.next_back()
+        } else {
+            iter.next()
+        } {
+            *item += 100;
+            from_back = !from_back;
+        }
+        assert_eq!(
+            buf.iter().copied().collect::<Vec<_>>(),
+            vec![101, 102, 103, 104, 105],
+            "Every item should have been mutated exactly once!"
+        );
+
+        for (i, item) in buf.iter_mut().rev().enumerate() {
+            *item = i;
+        }
+        assert_eq!(
+            buf.iter().copied().collect::<Vec<_>>(),
+            vec![4, 3, 2, 1, 0],
+            "iter_mut().rev() should visit the items from back to front!"
+        );
+    }
+
+    #[test]
+    fn test_group_consecutive() {
+        let mut buf = RingBuffer::<usize, 8>::new();
+        for _ in 0..5 {
+            buf.push(0);
+            buf.pop_front();
+        }
+        for i in [1, 1, 2, 2, 2, 1, 3] {
+            buf.push(i);
+        }
+        assert!(
+            !buf.is_contiguous(),
+            "The items should wrap around the array!"
+        );
+
+        assert_eq!(
+            buf.group_consecutive(|&item| item),
+            vec![vec![&1, &1], vec![&2, &2, &2], vec![&1], vec![&3]],
+            "Expected a group per run of equal keys!"
+        );
+        assert_eq!(
+            buf.group_consecutive(|&item| item % 2).len(),
+            3,
+            "Runs of odd and even keys should be grouped!"
+        );
+        assert!(
+            RingBuffer::<usize, 4>::new()
+                .group_consecutive(|&item| item)
+                .is_empty(),
+            "An empty RingBuffer should have no groups!"
+        );
+    }
+
+    #[test]
+    fn test_to_vec_deque() {
+        let buf = wrapped_buffer();
+
+        let deque = buf.to_vec_deque();
+        assert_eq!(
+            deque,
+            VecDeque::from([1, 2, 3, 4, 5]),
+            "The VecDeque should hold the items in order!"
+        );
+        assert!(
+            deque.capacity() >= buf.capacity(),
+            "The VecDeque should have room for capacity() items!"
+        );
+        assert_eq!(
+            buf.iter().copied().collect::<Vec<_>>(),
+            vec![1, 2, 3, 4, 5],
+            "The original buffer should be unchanged!"
+        );
+    }
+
+    #[test]
+    fn test_replace_front_and_back() {
+        let mut buf = wrapped_buffer();
+
+        assert_eq!(
+            buf.replace_front(10),
+            Some(1),
+            "Expected the old front item!"
+        );
+        assert_eq!(buf.replace_back(50), Some(5), "Expected the old back item!");
+        assert_eq!(
+            buf.iter().copied().collect::<Vec<_>>(),
+            vec![10, 2, 3, 4, 50],
+            "Only the end items should have been replaced!"
+        );
+
+        let mut empty = RingBuffer::<usize, 4>::new();
+        assert_eq!(
+            empty.replace_front(1),
+            None,
+            "Nothing should be replaced in an empty RingBuffer!"
+        );
+        assert_eq!(
+            empty.replace_back(1),
+            None,
+            "Nothing should be replaced in an empty RingBuffer!"
+        );
+        assert!(empty.is_empty(), "Nothing should have been stored!");
+    }
+
+    #[test]
+    fn test_trim_to() {
+        let mut buf: RingBuffer<usize, 9> = (1..=8).collect();
+
+        assert_eq!(
+            buf.trim_to(4),
+            vec![1, 2, 3, 4],
+            "Expected the oldest items in order!"
+        );
+        assert_eq!(
+            buf.iter().copied().collect::<Vec<_>>(),
+            vec![5, 6, 7, 8],
+            "Expected the newest items to be retained!"
+        );
+        assert!(
+            buf.trim_to(10).is_empty(),
+            "Trimming to more than len() should remove nothing!"
+        );
+    }
+
+    #[test]
+    fn test_consume_with() {
+        let mut buf = wrapped_buffer();
+
+        let mut consumed = Vec::new();
+        buf.consume_with(|item| consumed.push(item));
+        assert_eq!(
+            consumed,
+            vec![1, 2, 3, 4, 5],
+            "The items should be consumed from front to back!"
+        );
+        assert!(
+            buf.is_empty(),
+            "The RingBuffer should be empty after consuming!"
+        );
+    }
+
+    #[test]
+    fn test_peek_nth_mut() {
+        let mut buf = wrapped_buffer();
+
+        if let Some(item) = buf.peek_nth_mut(1) {
+            *item = 20;
+        }
+        assert_eq!(
+            buf.iter().copied().collect::<Vec<_>>(),
+            vec![1, 20, 3, 4, 5],
+            "Only the second item should have been mutated!"
+        );
+        assert!(
+            buf.peek_nth_mut(5).is_none(),
+            "An index past len() should be out of range!"
+        );
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let mut buf = wrapped_buffer();
+
+        let snapshot = buf.snapshot();
+        assert_eq!(
+            snapshot,
+            buf.iter().copied().collect::<Vec<_>>(),
+            "The snapshot should match the iter() order!"
+        );
+
+        buf.rotate_until(|&item| item == 3);
+        buf.push_overwrite(6);
+        assert_eq!(
+            snapshot,
+            vec![1, 2, 3, 4, 5],
+            "The snapshot should not follow later changes!"
+        );
+    }
+
+    #[test]
+    fn test_for_each_window_mut() {
+        let mut buf: RingBuffer<usize, 8> = [3, 9, 3, 9, 3, 0].into_iter().collect();
+        buf.pop_front();
+        buf.push(9);
+        buf.push(9);
+
+        let smooth = |window: &mut [usize]| window[1] = window.iter().sum::<usize>() / 3;
+        let mut expected = buf.iter().copied().collect::<Vec<_>>();
+        for start in 0..expected.len() - 2 {
+            smooth(&mut expected[start..start + 3]);
+        }
+
+        buf.for_each_window_mut(3, smooth);
+        assert_eq!(
+            buf.iter().copied().collect::<Vec<_>>(),
+            expected,
+            "Each window should be smoothed in order, seeing the earlier edits!"
+        );
+        assert_eq!(buf.len(), 7, "Smoothing should keep every item!");
+
+        let mut calls = 0;
+        buf.for_each_window_mut(8, |_| calls += 1);
+        assert_eq!(
+            calls, 0,
+            "A window larger than len() should never be visited!"
+        );
+    }
+
+    #[test]
+    fn test_for_each_window_mut_panicking_callback() {
+        let mut buf: RingBuffer<usize, 6> = [1, 2, 3, 4].into_iter().collect();
+
+        let mut calls = 0;
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            buf.for_each_window_mut(2, |window| {
+                calls += 1;
+                assert!(calls != 2, "callback panics");
+                window[0] += 10;
+            });
+        }));
+        assert!(result.is_err(), "Expected the callback to panic!");
+        assert_eq!(
+            buf.iter().copied().collect::<Vec<_>>(),
+            vec![11, 2, 3, 4],
+            "Every item should survive a panic, keeping the edits made before it!"
+        );
+    }
+
+    #[test]
+    fn test_try_front_and_back() {
+        let empty = RingBuffer::<usize, 4>::new();
+        assert_eq!(
+            empty.try_front(),
+            Err(EmptyError),
+            "An empty RingBuffer should report EmptyError for the front!"
+        );
+        assert_eq!(
+            empty.try_back(),
+            Err(EmptyError),
+            "An empty RingBuffer should report EmptyError for the back!"
+        );
+
+        let buf = wrapped_buffer();
+        assert_eq!(buf.try_front(), Ok(&1), "Expected the front item!");
+        assert_eq!(buf.try_back(), Ok(&5), "Expected the back item!");
+    }
+
+    #[test]
+    fn test_retain_indexed() {
+        let mut buf = wrapped_buffer();
+        buf.replace(0, 10);
+
+        buf.retain_indexed(|i, _| i % 2 == 0);
+        assert_eq!(
+            buf.iter().copied().collect::<Vec<_>>(),
+            vec![10, 3, 5],
+            "Only the even-indexed items should be kept, in order!"
+        );
+        assert_eq!(buf.len(), 3, "Expected three items to remain!");
+        assert_eq!(buf.get_back(), Some(5), "The back should be Some(5)!");
+
+        buf.push(7);
+        assert_eq!(
+            buf.iter().copied().collect::<Vec<_>>(),
+            vec![10, 3, 5, 7],
+            "Pushing after retaining should append at the new back!"
+        );
+
+        buf.retain_indexed(|_, _| false);
+        assert!(
+            buf.is_empty(),
+            "Retaining nothing should empty the RingBuffer!"
+        );
+    }
+
+    #[test]
+    fn test_retain_indexed_panicking_predicate() {
+        let mut buf = wrapped_buffer();
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            buf.retain_indexed(|i, _| {
+                assert!(i != 3, "predicate panics");
+                i != 1
+            });
+        }));
+        assert!(result.is_err(), "Expected the predicate to panic!");
+        assert_eq!(
+            buf.len(),
+            buf.iter().count(),
+            "len() should match the items yielded after a panic!"
+        );
+        assert_eq!(
+            buf.iter().copied().collect::<Vec<_>>(),
+            vec![1, 3, 4, 5],
+            "The items not visited yet should be kept after the removed one!"
+        );
+        assert_eq!(buf.pop_front(), Some(1), "The front should still pop!");
+        assert_eq!(buf.get_back(), Some(5), "The back should be Some(5)!");
+    }
+
+    #[test]
+    fn test_iter_with_slot() {
+        let buf = wrapped_buffer();
+
+        assert_eq!(
+            buf.iter_with_slot().collect::<Vec<_>>(),
+            vec![(4, &1), (5, &2), (0, &3), (1, &4), (2, &5)],
+            "The slot indices should wrap around the end of the array!"
+        );
+        for (slot, item) in buf.iter_with_slot() {
+            assert_eq!(
+                buf.data[slot].as_ref(),
+                Some(item),
+                "Every slot index should point at its item!"
+            );
+        }
     }
 }